@@ -0,0 +1,280 @@
+//! 帮助菜单命令行工具
+//!
+//! 无需连接机器人即可校验 `config.toml`，并将菜单渲染为图片、HTML 或文本，
+//! 便于调整配置与模板时快速预览。
+//!
+//! ```text
+//! help-center config.toml                       # 校验并渲染主菜单为 menu.jpg
+//! help-center config.toml -f html -g 123456789  # 指定群的菜单 HTML
+//! help-center config.toml -c 2 -f text          # 第 2 个分类的纯文本菜单
+//! help-center config.toml -s 签到               # 打印搜索结果
+//! help-center config.toml -a -f text            # 管理员可见的纯文本菜单
+//! ```
+
+use kovi_plugin_help_center::config::{Config, ImageFormat, Scope, TextFormat, Viewer};
+use kovi_plugin_help_center::{export, render, text, validate};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+const USAGE: &str = "\
+用法: help-center <配置文件> [选项]
+
+选项:
+  -f, --format <格式>     输出格式：image（默认，按配置的 [render] 格式）、png、jpeg、webp、
+                          html、export、text、markdown
+  -o, --output <路径>     输出文件，默认为 menu.<图片扩展名> / menu.html / help.html，文本默认打印到终端
+  -g, --group <群号>      使用指定群的覆盖配置
+  -p, --private           使用私聊覆盖配置
+  -a, --admin             以管理员身份查看，包含仅管理员可见的条目
+  -c, --category <分类>   只输出指定分类（分类名或序号）
+  -s, --search <关键词>   打印搜索结果，不渲染菜单
+  -h, --help              显示本帮助
+
+配置文件同目录下的 templates/ 中的覆盖模板会一并加载。";
+
+/// 输出格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    /// 渲染后的图片（需要 Chrome / Chromium），可覆盖配置中的图片格式
+    Image(Option<ImageFormat>),
+    /// 交给浏览器渲染的 HTML
+    Html,
+    /// 可独立打开的网页版菜单
+    Export,
+    Text,
+    Markdown,
+}
+
+impl Format {
+    fn parse(value: &str) -> Result<Self, String> {
+        match value.to_ascii_lowercase().as_str() {
+            "image" => Ok(Format::Image(None)),
+            "png" => Ok(Format::Image(Some(ImageFormat::Png))),
+            "jpeg" | "jpg" => Ok(Format::Image(Some(ImageFormat::Jpeg))),
+            "webp" => Ok(Format::Image(Some(ImageFormat::Webp))),
+            "html" => Ok(Format::Html),
+            "export" => Ok(Format::Export),
+            "text" | "txt" => Ok(Format::Text),
+            "markdown" | "md" => Ok(Format::Markdown),
+            _ => Err(format!("未知的输出格式「{}」", value)),
+        }
+    }
+
+    fn default_output(self, image: ImageFormat) -> Option<String> {
+        match self {
+            Format::Image(_) => Some(format!("menu.{}", image.extension())),
+            Format::Html => Some("menu.html".into()),
+            Format::Export => Some("help.html".into()),
+            Format::Text | Format::Markdown => None,
+        }
+    }
+}
+
+struct Args {
+    config: PathBuf,
+    format: Format,
+    output: Option<PathBuf>,
+    scope: Option<Scope>,
+    viewer: Viewer,
+    category: Option<String>,
+    search: Option<String>,
+}
+
+/// 解析命令行参数，请求帮助时返回 `None`
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Option<Args>, String> {
+    let mut config = None;
+    let mut parsed = Args {
+        config: PathBuf::new(),
+        format: Format::Image(None),
+        output: None,
+        scope: None,
+        viewer: Viewer::Member,
+        category: None,
+        search: None,
+    };
+
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| {
+            args.next()
+                .ok_or_else(|| format!("选项 {} 缺少参数值", name))
+        };
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(None),
+            "-f" | "--format" => parsed.format = Format::parse(&value(&arg)?)?,
+            "-o" | "--output" => parsed.output = Some(PathBuf::from(value(&arg)?)),
+            "-g" | "--group" => {
+                let id = value(&arg)?;
+                let id = id
+                    .parse()
+                    .map_err(|_| format!("「{}」不是有效的群号", id))?;
+                parsed.scope = Some(Scope::Group(id));
+            }
+            "-p" | "--private" => parsed.scope = Some(Scope::Private),
+            "-a" | "--admin" => parsed.viewer = Viewer::Admin,
+            "-c" | "--category" => parsed.category = Some(value(&arg)?),
+            "-s" | "--search" => parsed.search = Some(value(&arg)?),
+            _ if arg.starts_with('-') => return Err(format!("未知选项 {}", arg)),
+            _ if config.is_none() => config = Some(PathBuf::from(arg)),
+            _ => return Err(format!("多余的参数 {}", arg)),
+        }
+    }
+
+    parsed.config = config.ok_or("缺少配置文件路径")?;
+    Ok(Some(parsed))
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let args = match parse_args(std::env::args().skip(1)) {
+        Ok(Some(args)) => args,
+        Ok(None) => {
+            println!("{}", USAGE);
+            return ExitCode::SUCCESS;
+        }
+        Err(e) => {
+            eprintln!("❌ {}\n\n{}", e, USAGE);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let result = run(&args).await;
+    if matches!(args.format, Format::Image(_)) && args.search.is_none() {
+        cdp_html_shot::Browser::shutdown_global().await;
+    }
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("❌ {:#}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+async fn run(args: &Args) -> anyhow::Result<()> {
+    let mut config = load_config(&args.config)?;
+    if let Format::Image(Some(format)) = args.format {
+        config.render.format = format;
+    }
+
+    let (config, variant) = match args.scope {
+        Some(scope) => config.resolve(scope),
+        None => (config.merged(), "default".to_string()),
+    };
+    let config = config.visible_to(args.viewer);
+    if args.scope.is_some() && variant == "default" {
+        eprintln!("⚠️ 配置中没有对应的覆盖项，使用全局配置");
+    }
+
+    if let Some(keyword) = &args.search {
+        let results = config.search(keyword);
+        if results.is_empty() {
+            println!("🔍 未找到与「{}」相关的指令", keyword);
+        } else {
+            println!("{}", text::search(keyword, &results));
+        }
+        return Ok(());
+    }
+
+    let category = match &args.category {
+        Some(keyword) => Some(config.find_category(keyword).ok_or_else(|| {
+            let names: Vec<_> = config.category.iter().map(|c| c.name.as_str()).collect();
+            anyhow::anyhow!("未找到分类「{}」，可选：{}", keyword, names.join("、"))
+        })?),
+        None => None,
+    };
+    let categories = category.map_or(config.category.as_slice(), std::slice::from_ref);
+
+    match args.format {
+        Format::Text | Format::Markdown => {
+            let format = match args.format {
+                Format::Markdown => TextFormat::Markdown,
+                _ => TextFormat::Plain,
+            };
+            let menu = text::menu(&config, categories, format);
+            match &args.output {
+                Some(path) => write(path, menu.as_bytes())?,
+                None => println!("{}", menu),
+            }
+        }
+        Format::Export => {
+            let mut view = config.clone();
+            view.category = categories.to_vec();
+            let data_dir = config.config_path.parent().unwrap_or(Path::new("."));
+            let html = export::build(&view, data_dir)?;
+            write(&output(args, &config), html.as_bytes())?;
+        }
+        Format::Html | Format::Image(_) => {
+            let pages = match category {
+                Some(category) => vec![render::build_category_html(&config, category)?],
+                None => render::paginate(&config)
+                    .iter()
+                    .map(|page| render::build_html(&config, page))
+                    .collect::<anyhow::Result<_>>()?,
+            };
+
+            let base = output(args, &config);
+            for (i, html) in pages.iter().enumerate() {
+                let path = page_path(&base, i + 1, pages.len());
+                if args.format == Format::Html {
+                    write(&path, html.as_bytes())?;
+                } else {
+                    render::render_image(html, &path, &config.render).await?;
+                    println!("✅ 已生成 {}", path.display());
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// 读取并校验配置，加载同目录下的覆盖模板
+fn load_config(path: &Path) -> anyhow::Result<Config> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| anyhow::anyhow!("读取 {} 失败: {}", path.display(), e))?;
+
+    let (mut config, warnings) = validate::parse(&content)
+        .map_err(|issues| anyhow::anyhow!("配置校验未通过：\n{}", validate::report(&issues)))?;
+    for issue in &warnings {
+        eprintln!("{}", issue);
+    }
+    config.config_path = path.to_path_buf();
+
+    let data_dir = path.parent().unwrap_or(Path::new("."));
+    match render::load_templates(data_dir, &config)? {
+        0 => {}
+        count => eprintln!("📄 已加载 {} 个覆盖模板", count),
+    }
+    Ok(config)
+}
+
+fn output(args: &Args, config: &Config) -> PathBuf {
+    args.output
+        .clone()
+        .or_else(|| {
+            args.format
+                .default_output(config.render.format)
+                .map(PathBuf::from)
+        })
+        .unwrap_or_default()
+}
+
+/// 多页菜单的第 `number` 页输出路径：`menu.jpg` → `menu-2.jpg`
+fn page_path(base: &Path, number: usize, total: usize) -> PathBuf {
+    if total == 1 {
+        return base.to_path_buf();
+    }
+    let stem = base.file_stem().unwrap_or_default().to_string_lossy();
+    let name = match base.extension() {
+        Some(ext) => format!("{}-{}.{}", stem, number, ext.to_string_lossy()),
+        None => format!("{}-{}", stem, number),
+    };
+    base.with_file_name(name)
+}
+
+fn write(path: &Path, bytes: &[u8]) -> anyhow::Result<()> {
+    std::fs::write(path, bytes)
+        .map_err(|e| anyhow::anyhow!("写入 {} 失败: {}", path.display(), e))?;
+    println!("✅ 已生成 {}", path.display());
+    Ok(())
+}
@@ -6,37 +6,383 @@
 //! - 🎨 玻璃拟态 UI 设计，支持自定义主题
 //! - 📱 **移动端优先设计**，竖屏阅读更舒适
 //! - 📁 分类展示，层次清晰
-//! - 🔍 支持按名称/指令搜索
+//! - 🔍 支持按名称/指令搜索，模糊与拼音匹配，按相关度排序
 //! - 💾 智能缓存，配置变更自动刷新
 //! - 🔄 支持热重载配置
+//! - 🧩 其他插件可通过 [`HelpRegistry`] 在运行时注册帮助条目
 
 // ============================================================================
 //                              配置模块
 // ============================================================================
-mod config {
+pub mod config {
+    use super::registry::HelpRegistry;
+    use super::search;
+    use super::validate::{self, Issue};
     use kovi::toml;
     use kovi::utils::load_toml_data;
     use serde::{Deserialize, Serialize};
-    use std::hash::{Hash, Hasher};
-    use std::path::PathBuf;
+    use std::collections::BTreeMap;
+    use std::hash::Hash;
+    use std::path::{Path, PathBuf};
     use std::sync::{Arc, OnceLock, RwLock};
 
     pub static CONFIG: OnceLock<Arc<RwLock<Config>>> = OnceLock::new();
 
     const DEFAULT_CONFIG: &str = include_str!("default_config.toml");
 
-    #[derive(Debug, Serialize, Deserialize, Clone, Hash)]
+    #[derive(Debug, Serialize, Deserialize, Clone, Hash, Default)]
     pub struct PluginItem {
         pub name: String,
         pub desc: String,
         #[serde(default)]
-        pub commands: Vec<String>,
+        pub commands: Vec<CommandItem>,
         /// 可选的图标 emoji
         #[serde(default)]
         pub icon: Option<String>,
+        /// 别名（用于「帮助 插件名」精确匹配详情页）
+        #[serde(default)]
+        pub aliases: Vec<String>,
+        /// 搜索标签
+        #[serde(default)]
+        pub tags: Vec<String>,
+        /// 详细用法说明
+        #[serde(default)]
+        pub usage: Option<String>,
+        /// 参数说明
+        #[serde(default)]
+        pub params: Vec<PluginParam>,
+        /// 使用示例
+        #[serde(default)]
+        pub examples: Vec<String>,
+        /// 注意事项
+        #[serde(default)]
+        pub notes: Vec<String>,
+        /// 隐藏：不出现在任何菜单与搜索结果中
+        #[serde(default)]
+        pub hidden: bool,
+        /// 停用：仍会展示，但以灰色显示并附带停用原因
+        #[serde(default)]
+        pub disabled: bool,
+        /// 停用原因
+        #[serde(default)]
+        pub disabled_reason: Option<String>,
+        /// 仅对管理员展示
+        #[serde(default)]
+        pub admin_only: bool,
+    }
+
+    /// 结构化指令定义
+    ///
+    /// 兼容旧版纯字符串写法，如 `"##名字 模型 提示词 (创建)"`：
+    /// 首个词作为触发词，其余作为参数，末尾括号内容作为说明。
+    #[derive(Debug, Serialize, Deserialize, Clone, Hash, Default)]
+    #[serde(from = "CommandRepr")]
+    pub struct CommandItem {
+        /// 触发词
+        pub trigger: String,
+        /// 参数列表
+        pub args: Vec<CommandArg>,
+        /// 指令说明
+        pub desc: Option<String>,
+        /// 触发词别名
+        pub aliases: Vec<String>,
+        /// 使用示例
+        pub example: Option<String>,
     }
 
     #[derive(Debug, Serialize, Deserialize, Clone, Hash)]
+    pub struct CommandArg {
+        pub name: String,
+        /// 是否必填
+        #[serde(default = "default_required")]
+        pub required: bool,
+        /// 参数类型（如 文本、数字、图片）
+        #[serde(default, rename = "type")]
+        pub kind: Option<String>,
+    }
+
+    fn default_required() -> bool {
+        true
+    }
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum CommandRepr {
+        Text(String),
+        Table {
+            trigger: String,
+            #[serde(default)]
+            args: Vec<CommandArg>,
+            #[serde(default)]
+            desc: Option<String>,
+            #[serde(default)]
+            aliases: Vec<String>,
+            #[serde(default)]
+            example: Option<String>,
+        },
+    }
+
+    impl From<CommandRepr> for CommandItem {
+        fn from(repr: CommandRepr) -> Self {
+            match repr {
+                CommandRepr::Text(text) => CommandItem::parse(&text),
+                CommandRepr::Table {
+                    trigger,
+                    args,
+                    desc,
+                    aliases,
+                    example,
+                } => CommandItem {
+                    trigger,
+                    args,
+                    desc,
+                    aliases,
+                    example,
+                },
+            }
+        }
+    }
+
+    impl CommandItem {
+        pub fn new(trigger: impl Into<String>) -> Self {
+            Self {
+                trigger: trigger.into(),
+                ..Default::default()
+            }
+        }
+
+        /// 解析旧版纯字符串指令
+        pub fn parse(text: &str) -> Self {
+            let text = text.trim();
+            // 空括号 `签到 ()` 不作为说明
+            let (syntax, desc) = split_trailing_note(text)
+                .map(|(syntax, note)| (syntax, Some(note.to_string()).filter(|n| !n.is_empty())))
+                .unwrap_or((text, None));
+
+            let mut tokens = syntax.split_whitespace();
+            let trigger = tokens.next().unwrap_or_default().to_string();
+            let args = tokens.map(CommandArg::parse).collect();
+
+            Self {
+                trigger,
+                args,
+                desc,
+                ..Default::default()
+            }
+        }
+
+        pub fn with_arg(mut self, arg: CommandArg) -> Self {
+            self.args.push(arg);
+            self
+        }
+
+        pub fn with_desc(mut self, desc: impl Into<String>) -> Self {
+            self.desc = Some(desc.into());
+            self
+        }
+
+        pub fn with_alias(mut self, alias: impl Into<String>) -> Self {
+            self.aliases.push(alias.into());
+            self
+        }
+
+        pub fn with_example(mut self, example: impl Into<String>) -> Self {
+            self.example = Some(example.into());
+            self
+        }
+
+        /// 指令语法，如 `签到 <次数> [备注]`
+        pub fn syntax(&self) -> String {
+            let mut out = self.trigger.clone();
+            for arg in &self.args {
+                out.push(' ');
+                out.push_str(&arg.display());
+            }
+            out
+        }
+
+        /// 参与搜索的文本：触发词与别名
+        pub fn search_terms(&self) -> impl Iterator<Item = &str> {
+            std::iter::once(self.trigger.as_str()).chain(self.aliases.iter().map(String::as_str))
+        }
+    }
+
+    impl From<&str> for CommandItem {
+        fn from(text: &str) -> Self {
+            CommandItem::parse(text)
+        }
+    }
+
+    impl From<String> for CommandItem {
+        fn from(text: String) -> Self {
+            CommandItem::parse(&text)
+        }
+    }
+
+    impl CommandArg {
+        pub fn required(name: impl Into<String>) -> Self {
+            Self {
+                name: name.into(),
+                required: true,
+                kind: None,
+            }
+        }
+
+        pub fn optional(name: impl Into<String>) -> Self {
+            Self {
+                name: name.into(),
+                required: false,
+                kind: None,
+            }
+        }
+
+        pub fn with_type(mut self, kind: impl Into<String>) -> Self {
+            self.kind = Some(kind.into());
+            self
+        }
+
+        /// 解析旧版参数写法：`<必填>`、`[可选]` 或裸词（视为必填）
+        fn parse(token: &str) -> Self {
+            if let Some(name) = token.strip_prefix('[').and_then(|t| t.strip_suffix(']')) {
+                Self::optional(name)
+            } else if let Some(name) = token.strip_prefix('<').and_then(|t| t.strip_suffix('>')) {
+                Self::required(name)
+            } else {
+                Self::required(token)
+            }
+        }
+
+        /// 参数显示形式：必填 `<名称>`，可选 `[名称]`
+        pub fn display(&self) -> String {
+            if self.required {
+                format!("<{}>", self.name)
+            } else {
+                format!("[{}]", self.name)
+            }
+        }
+    }
+
+    /// 拆分末尾括号说明，支持半角与全角括号
+    fn split_trailing_note(text: &str) -> Option<(&str, &str)> {
+        [('(', ')'), ('（', '）')]
+            .into_iter()
+            .find_map(|(open, close)| {
+                let inner = text.strip_suffix(close)?;
+                let start = inner.rfind(open)?;
+                Some((
+                    inner[..start].trim_end(),
+                    inner[start + open.len_utf8()..].trim(),
+                ))
+            })
+    }
+
+    #[cfg(test)]
+    mod command_tests {
+        use super::*;
+
+        fn args(cmd: &CommandItem) -> Vec<(&str, bool)> {
+            cmd.args
+                .iter()
+                .map(|a| (a.name.as_str(), a.required))
+                .collect()
+        }
+
+        #[test]
+        fn parses_trigger_args_and_note() {
+            let cmd = CommandItem::parse("  ##名字 模型 <提示词> [备注] (创建)  ");
+            assert_eq!(cmd.trigger, "##名字");
+            assert_eq!(
+                args(&cmd),
+                [("模型", true), ("提示词", true), ("备注", false)]
+            );
+            assert_eq!(cmd.desc.as_deref(), Some("创建"));
+            assert_eq!(cmd.syntax(), "##名字 <模型> <提示词> [备注]");
+        }
+
+        #[test]
+        fn full_width_and_unspaced_notes() {
+            let cmd = CommandItem::parse("签到（每日一次）");
+            assert_eq!(cmd.trigger, "签到");
+            assert_eq!(cmd.desc.as_deref(), Some("每日一次"));
+
+            let cmd = CommandItem::parse("抽卡 [次数]（ 十连 ）");
+            assert_eq!(args(&cmd), [("次数", false)]);
+            assert_eq!(cmd.desc.as_deref(), Some("十连"));
+
+            let cmd = CommandItem::parse("jrrp(查看人品)");
+            assert_eq!(cmd.trigger, "jrrp");
+            assert_eq!(cmd.desc.as_deref(), Some("查看人品"));
+        }
+
+        #[test]
+        fn empty_and_note_only_strings() {
+            let cmd = CommandItem::parse("   ");
+            assert_eq!(cmd.trigger, "");
+            assert!(cmd.args.is_empty() && cmd.desc.is_none());
+
+            // 只有说明时没有触发词，由配置校验报告
+            let cmd = CommandItem::parse("（查看列表）");
+            assert_eq!(cmd.trigger, "");
+            assert_eq!(cmd.desc.as_deref(), Some("查看列表"));
+
+            let cmd = CommandItem::parse("签到 ()");
+            assert_eq!(cmd.trigger, "签到");
+            assert!(cmd.desc.is_none());
+        }
+
+        #[test]
+        fn unbalanced_brackets_stay_in_syntax() {
+            let cmd = CommandItem::parse("签到 (每日");
+            assert_eq!(args(&cmd), [("(每日", true)]);
+            assert!(cmd.desc.is_none());
+
+            let cmd = CommandItem::parse("词意榜 </> <词意全榜>");
+            assert_eq!(args(&cmd), [("/", true), ("词意全榜", true)]);
+        }
+
+        #[test]
+        fn string_and_table_forms_deserialize() {
+            #[derive(Deserialize)]
+            struct Commands {
+                commands: Vec<CommandItem>,
+            }
+
+            let parsed: Commands = kovi::toml::from_str(
+                r#"
+                commands = [
+                    "运势 [对象] (抽取运势)",
+                    { trigger = "签到" },
+                    { trigger = "天气", args = [{ name = "城市" }, { name = "日期", required = false, type = "日期" }], aliases = ["tq"], desc = "查询天气", example = "天气 北京" },
+                ]
+                "#,
+            )
+            .unwrap();
+            let [text, bare, table] = parsed.commands.as_slice() else {
+                panic!("应解析出 3 条指令");
+            };
+
+            assert_eq!(text.syntax(), "运势 [对象]");
+            assert_eq!(text.desc.as_deref(), Some("抽取运势"));
+
+            assert_eq!(bare.trigger, "签到");
+            assert!(bare.args.is_empty() && bare.desc.is_none() && bare.aliases.is_empty());
+
+            assert_eq!(table.syntax(), "天气 <城市> [日期]");
+            assert_eq!(table.args[0].kind, None);
+            assert_eq!(table.args[1].kind.as_deref(), Some("日期"));
+            assert_eq!(table.search_terms().collect::<Vec<_>>(), ["天气", "tq"]);
+            assert_eq!(table.example.as_deref(), Some("天气 北京"));
+        }
+    }
+
+    #[derive(Debug, Serialize, Deserialize, Clone, Hash, Default)]
+    pub struct PluginParam {
+        pub name: String,
+        #[serde(default)]
+        pub desc: String,
+    }
+
+    #[derive(Debug, Serialize, Deserialize, Clone, Hash, Default)]
     pub struct Category {
         pub name: String,
         /// 分类图标
@@ -47,45 +393,480 @@ mod config {
         pub color: Option<String>,
         #[serde(default)]
         pub plugins: Vec<PluginItem>,
+        /// 隐藏整个分类
+        #[serde(default)]
+        pub hidden: bool,
+        /// 停用整个分类：分类下的插件均以灰色显示
+        #[serde(default)]
+        pub disabled: bool,
+        /// 停用原因，插件未单独填写时沿用
+        #[serde(default)]
+        pub disabled_reason: Option<String>,
+        /// 整个分类仅对管理员展示
+        #[serde(default)]
+        pub admin_only: bool,
     }
 
-    #[derive(Debug, Serialize, Deserialize, Clone)]
+    impl PluginItem {
+        pub fn new(name: impl Into<String>, desc: impl Into<String>) -> Self {
+            Self {
+                name: name.into(),
+                desc: desc.into(),
+                ..Default::default()
+            }
+        }
+
+        pub fn with_icon(mut self, icon: impl Into<String>) -> Self {
+            self.icon = Some(icon.into());
+            self
+        }
+
+        pub fn with_command(mut self, cmd: impl Into<CommandItem>) -> Self {
+            self.commands.push(cmd.into());
+            self
+        }
+
+        pub fn with_commands<I, S>(mut self, cmds: I) -> Self
+        where
+            I: IntoIterator<Item = S>,
+            S: Into<CommandItem>,
+        {
+            self.commands.extend(cmds.into_iter().map(Into::into));
+            self
+        }
+
+        pub fn with_alias(mut self, alias: impl Into<String>) -> Self {
+            self.aliases.push(alias.into());
+            self
+        }
+
+        pub fn with_tag(mut self, tag: impl Into<String>) -> Self {
+            self.tags.push(tag.into());
+            self
+        }
+
+        pub fn with_usage(mut self, usage: impl Into<String>) -> Self {
+            self.usage = Some(usage.into());
+            self
+        }
+
+        pub fn with_param(mut self, name: impl Into<String>, desc: impl Into<String>) -> Self {
+            self.params.push(PluginParam {
+                name: name.into(),
+                desc: desc.into(),
+            });
+            self
+        }
+
+        pub fn with_example(mut self, example: impl Into<String>) -> Self {
+            self.examples.push(example.into());
+            self
+        }
+
+        pub fn with_note(mut self, note: impl Into<String>) -> Self {
+            self.notes.push(note.into());
+            self
+        }
+
+        pub fn with_hidden(mut self) -> Self {
+            self.hidden = true;
+            self
+        }
+
+        pub fn with_disabled(mut self, reason: impl Into<String>) -> Self {
+            self.disabled = true;
+            self.disabled_reason = Some(reason.into()).filter(|r: &String| !r.is_empty());
+            self
+        }
+
+        pub fn with_admin_only(mut self) -> Self {
+            self.admin_only = true;
+            self
+        }
+
+        /// 名称或别名是否与关键词完全一致（不区分大小写）
+        pub fn is_named(&self, keyword: &str) -> bool {
+            let kw = keyword.to_lowercase();
+            self.name.to_lowercase() == kw || self.aliases.iter().any(|a| a.to_lowercase() == kw)
+        }
+    }
+
+    impl Category {
+        pub fn new(name: impl Into<String>) -> Self {
+            Self {
+                name: name.into(),
+                ..Default::default()
+            }
+        }
+
+        pub fn with_icon(mut self, icon: impl Into<String>) -> Self {
+            self.icon = icon.into();
+            self
+        }
+
+        pub fn with_color(mut self, color: impl Into<String>) -> Self {
+            self.color = Some(color.into());
+            self
+        }
+
+        pub fn with_plugin(mut self, plugin: PluginItem) -> Self {
+            self.plugins.push(plugin);
+            self
+        }
+
+        pub fn with_hidden(mut self) -> Self {
+            self.hidden = true;
+            self
+        }
+
+        pub fn with_disabled(mut self, reason: impl Into<String>) -> Self {
+            self.disabled = true;
+            self.disabled_reason = Some(reason.into()).filter(|r: &String| !r.is_empty());
+            self
+        }
+
+        pub fn with_admin_only(mut self) -> Self {
+            self.admin_only = true;
+            self
+        }
+    }
+
+    /// 主题配置：选择预设，并可逐项覆盖预设中的颜色
+    #[derive(Debug, Serialize, Deserialize, Clone, Default)]
     pub struct Theme {
+        /// 预设主题名，默认 `light`
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub preset: Option<String>,
         /// 主色调
-        #[serde(default = "default_primary")]
-        pub primary: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub primary: Option<String>,
+        /// 辅助色，与主色组成渐变
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub secondary: Option<String>,
         /// 背景渐变起始色
-        #[serde(default = "default_bg_start")]
-        pub bg_start: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub bg_start: Option<String>,
         /// 背景渐变结束色
-        #[serde(default = "default_bg_end")]
-        pub bg_end: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub bg_end: Option<String>,
         /// 卡片背景透明度 0.0-1.0
-        #[serde(default = "default_card_opacity")]
-        pub card_opacity: f32,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub card_opacity: Option<f32>,
+        /// 卡片底色
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub surface: Option<String>,
+        /// 次级底色（计数、参数标签、示例代码）
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub surface_alt: Option<String>,
+        /// 标题文字
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub text: Option<String>,
+        /// 正文文字
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub text_secondary: Option<String>,
+        /// 说明文字
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub text_muted: Option<String>,
+        /// 弱化文字与分隔线
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub text_faint: Option<String>,
+        /// 主色块上的文字
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub on_primary: Option<String>,
+        /// 搜索高亮底色
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub highlight: Option<String>,
+        /// 阴影颜色
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub shadow: Option<String>,
+        /// 夜间自动切换
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub night: Option<NightMode>,
     }
 
-    fn default_primary() -> String {
-        "#6366f1".into()
+    /// 夜间模式：在指定时段内改用另一套预设
+    #[derive(Debug, Serialize, Deserialize, Clone)]
+    pub struct NightMode {
+        /// 夜间使用的预设
+        #[serde(default = "default_night_preset")]
+        pub preset: String,
+        /// 开始小时（含），0-23
+        #[serde(default = "default_night_start")]
+        pub start: u32,
+        /// 结束小时（不含），0-23
+        #[serde(default = "default_night_end")]
+        pub end: u32,
     }
-    fn default_bg_start() -> String {
-        "#e0e7ff".into()
+
+    fn default_night_preset() -> String {
+        "dark".into()
     }
-    fn default_bg_end() -> String {
-        "#fdf4ff".into()
+    fn default_night_start() -> u32 {
+        19
     }
-    fn default_card_opacity() -> f32 {
-        0.90 // 提高不透明度，增强手机阅读时的对比度
+    fn default_night_end() -> u32 {
+        7
     }
 
-    impl Default for Theme {
-        fn default() -> Self {
-            Self {
-                primary: default_primary(),
-                bg_start: default_bg_start(),
-                bg_end: default_bg_end(),
-                card_opacity: default_card_opacity(),
+    impl NightMode {
+        /// 给定小时是否处于夜间时段，支持跨越零点
+        pub fn contains(&self, hour: u32) -> bool {
+            if self.start <= self.end {
+                (self.start..self.end).contains(&hour)
+            } else {
+                hour >= self.start || hour < self.end
+            }
+        }
+    }
+
+    /// 解析后的完整配色，模板中的 `theme` 即为此结构
+    #[derive(Debug, Serialize, Clone)]
+    pub struct Palette {
+        pub primary: String,
+        pub secondary: String,
+        pub bg_start: String,
+        pub bg_end: String,
+        pub card_opacity: f32,
+        pub surface: String,
+        pub surface_alt: String,
+        pub text: String,
+        pub text_secondary: String,
+        pub text_muted: String,
+        pub text_faint: String,
+        pub on_primary: String,
+        pub highlight: String,
+        pub shadow: String,
+    }
+
+    /// 内置预设
+    struct Preset {
+        primary: &'static str,
+        secondary: &'static str,
+        bg_start: &'static str,
+        bg_end: &'static str,
+        card_opacity: f32,
+        surface: &'static str,
+        surface_alt: &'static str,
+        text: &'static str,
+        text_secondary: &'static str,
+        text_muted: &'static str,
+        text_faint: &'static str,
+        on_primary: &'static str,
+        highlight: &'static str,
+        shadow: &'static str,
+    }
+
+    const PRESETS: [(&str, Preset); 6] = [
+        (
+            "light",
+            Preset {
+                primary: "#6366f1",
+                secondary: "#a855f7",
+                bg_start: "#e0e7ff",
+                bg_end: "#fdf4ff",
+                card_opacity: 0.90,
+                surface: "#ffffff",
+                surface_alt: "#f1f5f9",
+                text: "#1e293b",
+                text_secondary: "#334155",
+                text_muted: "#64748b",
+                text_faint: "#94a3b8",
+                on_primary: "#ffffff",
+                highlight: "rgba(250, 204, 21, 0.45)",
+                shadow: "#000000",
+            },
+        ),
+        (
+            "dark",
+            Preset {
+                primary: "#818cf8",
+                secondary: "#c084fc",
+                bg_start: "#0f172a",
+                bg_end: "#1e1b4b",
+                card_opacity: 0.85,
+                surface: "#1e293b",
+                surface_alt: "#334155",
+                text: "#f1f5f9",
+                text_secondary: "#e2e8f0",
+                text_muted: "#94a3b8",
+                text_faint: "#64748b",
+                on_primary: "#ffffff",
+                highlight: "rgba(250, 204, 21, 0.35)",
+                shadow: "#000000",
+            },
+        ),
+        (
+            "high-contrast",
+            Preset {
+                primary: "#1d4ed8",
+                secondary: "#6d28d9",
+                bg_start: "#ffffff",
+                bg_end: "#ffffff",
+                card_opacity: 1.0,
+                surface: "#ffffff",
+                surface_alt: "#e5e7eb",
+                text: "#000000",
+                text_secondary: "#111827",
+                text_muted: "#1f2937",
+                text_faint: "#374151",
+                on_primary: "#ffffff",
+                highlight: "#fde047",
+                shadow: "#000000",
+            },
+        ),
+        (
+            "sakura",
+            Preset {
+                primary: "#ec4899",
+                secondary: "#f472b6",
+                bg_start: "#fff1f2",
+                bg_end: "#fdf2f8",
+                card_opacity: 0.90,
+                surface: "#ffffff",
+                surface_alt: "#fce7f3",
+                text: "#500724",
+                text_secondary: "#831843",
+                text_muted: "#9d5b7b",
+                text_faint: "#c9a3b6",
+                on_primary: "#ffffff",
+                highlight: "rgba(250, 204, 21, 0.45)",
+                shadow: "#831843",
+            },
+        ),
+        (
+            "forest",
+            Preset {
+                primary: "#16a34a",
+                secondary: "#65a30d",
+                bg_start: "#ecfdf5",
+                bg_end: "#f7fee7",
+                card_opacity: 0.90,
+                surface: "#ffffff",
+                surface_alt: "#f0fdf4",
+                text: "#14532d",
+                text_secondary: "#166534",
+                text_muted: "#4d7c5a",
+                text_faint: "#86a98f",
+                on_primary: "#ffffff",
+                highlight: "rgba(250, 204, 21, 0.45)",
+                shadow: "#14532d",
+            },
+        ),
+        (
+            "ocean",
+            Preset {
+                primary: "#0284c7",
+                secondary: "#06b6d4",
+                bg_start: "#e0f2fe",
+                bg_end: "#ecfeff",
+                card_opacity: 0.90,
+                surface: "#ffffff",
+                surface_alt: "#f0f9ff",
+                text: "#0c4a6e",
+                text_secondary: "#075985",
+                text_muted: "#4b7190",
+                text_faint: "#8fb3c9",
+                on_primary: "#ffffff",
+                highlight: "rgba(250, 204, 21, 0.45)",
+                shadow: "#0c4a6e",
+            },
+        ),
+    ];
+
+    /// 所有内置预设名
+    pub fn preset_names() -> impl Iterator<Item = &'static str> {
+        PRESETS.iter().map(|(name, _)| *name)
+    }
+
+    /// 按名称查找预设（不区分大小写）
+    pub fn preset(name: &str) -> Option<Palette> {
+        let (_, p) = PRESETS
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name.trim()))?;
+        Some(Palette {
+            primary: p.primary.into(),
+            secondary: p.secondary.into(),
+            bg_start: p.bg_start.into(),
+            bg_end: p.bg_end.into(),
+            card_opacity: p.card_opacity,
+            surface: p.surface.into(),
+            surface_alt: p.surface_alt.into(),
+            text: p.text.into(),
+            text_secondary: p.text_secondary.into(),
+            text_muted: p.text_muted.into(),
+            text_faint: p.text_faint.into(),
+            on_primary: p.on_primary.into(),
+            highlight: p.highlight.into(),
+            shadow: p.shadow.into(),
+        })
+    }
+
+    impl Theme {
+        /// 颜色字段及其配置键名，用于校验
+        pub fn colors(&self) -> [(&'static str, Option<&String>); 13] {
+            [
+                ("primary", self.primary.as_ref()),
+                ("secondary", self.secondary.as_ref()),
+                ("bg_start", self.bg_start.as_ref()),
+                ("bg_end", self.bg_end.as_ref()),
+                ("surface", self.surface.as_ref()),
+                ("surface_alt", self.surface_alt.as_ref()),
+                ("text", self.text.as_ref()),
+                ("text_secondary", self.text_secondary.as_ref()),
+                ("text_muted", self.text_muted.as_ref()),
+                ("text_faint", self.text_faint.as_ref()),
+                ("on_primary", self.on_primary.as_ref()),
+                ("highlight", self.highlight.as_ref()),
+                ("shadow", self.shadow.as_ref()),
+            ]
+        }
+
+        /// 按当前本地时间解析配色
+        pub fn palette(&self) -> Palette {
+            use kovi::chrono::{Local, Timelike};
+            self.palette_at(Local::now().hour())
+        }
+
+        /// 按给定小时解析配色
+        ///
+        /// 夜间时段直接使用夜间预设，逐项覆盖的颜色只作用于白天的主题。
+        pub fn palette_at(&self, hour: u32) -> Palette {
+            if let Some(night) = &self.night
+                && night.contains(hour)
+                && let Some(palette) = preset(&night.preset)
+            {
+                return palette;
+            }
+
+            let mut p = self
+                .preset
+                .as_deref()
+                .and_then(preset)
+                .unwrap_or_else(|| preset("light").unwrap());
+
+            for (target, value) in [
+                (&mut p.primary, &self.primary),
+                (&mut p.secondary, &self.secondary),
+                (&mut p.bg_start, &self.bg_start),
+                (&mut p.bg_end, &self.bg_end),
+                (&mut p.surface, &self.surface),
+                (&mut p.surface_alt, &self.surface_alt),
+                (&mut p.text, &self.text),
+                (&mut p.text_secondary, &self.text_secondary),
+                (&mut p.text_muted, &self.text_muted),
+                (&mut p.text_faint, &self.text_faint),
+                (&mut p.on_primary, &self.on_primary),
+                (&mut p.highlight, &self.highlight),
+                (&mut p.shadow, &self.shadow),
+            ] {
+                if let Some(value) = value {
+                    *target = value.clone();
+                }
             }
+            if let Some(opacity) = self.card_opacity {
+                p.card_opacity = opacity;
+            }
+            p
         }
     }
 
@@ -109,463 +890,5358 @@ mod config {
         /// 触发词列表（可自定义）
         #[serde(default = "default_triggers")]
         pub triggers: Vec<String>,
+        /// 搜索结果回复方式
+        #[serde(default)]
+        pub search_reply: SearchReply,
+        /// 菜单与详情页的回复方式
+        #[serde(default)]
+        pub menu_reply: MenuReply,
+        /// 菜单或详情图片生成失败时的回退方式
+        #[serde(default)]
+        pub fallback: Fallback,
+        /// 管理员 QQ 号，可执行重载、清除缓存等管理指令
+        #[serde(default)]
+        pub admins: Vec<i64>,
+        /// 是否允许群主与群管理员执行管理指令
+        #[serde(default)]
+        pub allow_group_admin: bool,
+        /// 按群覆盖配置，键为群号
+        #[serde(default)]
+        pub groups: BTreeMap<String, ScopeOverride>,
+        /// 私聊覆盖配置
+        #[serde(default)]
+        pub private: Option<ScopeOverride>,
+        /// 是否监听配置文件变化并自动重载
+        #[serde(default = "default_watch_config")]
+        pub watch_config: bool,
+        /// 长菜单分页
+        #[serde(default)]
+        pub pagination: Pagination,
+        /// 启动与重载后的预渲染
+        #[serde(default)]
+        pub prerender: Prerender,
+        /// 图片发送方式
+        #[serde(default)]
+        pub image: ImageSetting,
+        /// 渲染超时、重试与熔断
+        #[serde(default)]
+        pub render: RenderSetting,
         /// 配置文件路径（内部使用）
         #[serde(skip)]
         pub config_path: PathBuf,
     }
 
-    fn default_title() -> String {
-        "📚 帮助中心".into()
+    /// 群聊或私聊的覆盖配置，未填写的项沿用全局配置
+    #[derive(Debug, Serialize, Deserialize, Clone, Default)]
+    pub struct ScopeOverride {
+        #[serde(default)]
+        pub title: Option<String>,
+        #[serde(default)]
+        pub subtitle: Option<String>,
+        #[serde(default)]
+        pub footer: Option<String>,
+        /// 整体替换主题
+        #[serde(default)]
+        pub theme: Option<Theme>,
+        /// 替换触发词列表
+        #[serde(default)]
+        pub triggers: Option<Vec<String>>,
+        /// 隐藏的分类名
+        #[serde(default)]
+        pub hide_categories: Vec<String>,
+        /// 隐藏的插件名
+        #[serde(default)]
+        pub hide_plugins: Vec<String>,
+        /// 仅在此处显示的额外分类，同名分类会合并插件
+        #[serde(default)]
+        pub category: Vec<Category>,
     }
-    fn default_footer() -> String {
-        "Powered by Kovi Framework".into()
+
+    /// 消息来源
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum Scope {
+        Group(i64),
+        Private,
     }
-    fn default_triggers() -> Vec<String> {
-        vec![
-            "help".into(),
-            "帮助".into(),
-            "菜单".into(),
-            "menu".into(),
-            "指令".into(),
-            "功能".into(),
-        ]
+
+    /// 搜索结果回复方式
+    #[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+    #[serde(rename_all = "lowercase")]
+    pub enum SearchReply {
+        /// 渲染为图片
+        #[default]
+        Image,
+        /// 纯文本列表
+        Text,
     }
 
-    impl Config {
-        pub fn load(data_dir: &PathBuf) -> Arc<RwLock<Self>> {
-            if !data_dir.exists() {
-                std::fs::create_dir_all(data_dir).expect("Failed to create data directory");
-            }
-            let config_path = data_dir.join("config.toml");
+    /// 菜单回复方式
+    #[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+    #[serde(rename_all = "lowercase")]
+    pub enum MenuReply {
+        /// 渲染为图片
+        #[default]
+        Image,
+        /// 纯文本
+        Text,
+        /// Markdown 文本
+        Markdown,
+    }
 
-            let default: Config =
-                toml::from_str(DEFAULT_CONFIG).expect("Default config parse error");
+    /// 菜单图片生成失败（浏览器不可用、渲染出错或超时）时的回退方式
+    #[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+    #[serde(rename_all = "lowercase")]
+    pub enum Fallback {
+        /// 仅提示失败
+        None,
+        /// 改发纯文本菜单
+        #[default]
+        Text,
+        /// 改发 Markdown 菜单
+        Markdown,
+    }
 
-            let mut config = load_toml_data(default, config_path.clone()).unwrap_or_else(|e| {
-                kovi::log::warn!("配置加载失败，使用默认配置: {}", e);
-                toml::from_str(DEFAULT_CONFIG).unwrap()
-            });
+    /// 文本菜单的格式
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum TextFormat {
+        Plain,
+        Markdown,
+    }
 
-            config.config_path = config_path;
-            Arc::new(RwLock::new(config))
+    impl MenuReply {
+        /// 以文本回复时的格式，图片回复时为 `None`
+        pub fn text_format(self) -> Option<TextFormat> {
+            match self {
+                MenuReply::Image => None,
+                MenuReply::Text => Some(TextFormat::Plain),
+                MenuReply::Markdown => Some(TextFormat::Markdown),
+            }
         }
+    }
 
-        /// 重新加载配置
-        pub fn reload(&mut self) -> Result<(), String> {
-            let new_config: Config = {
-                let content = std::fs::read_to_string(&self.config_path)
-                    .map_err(|e| format!("读取配置失败: {}", e))?;
-                toml::from_str(&content).map_err(|e| format!("解析配置失败: {}", e))?
-            };
-
-            self.title = new_config.title;
-            self.subtitle = new_config.subtitle;
-            self.footer = new_config.footer;
-            self.theme = new_config.theme;
-            self.category = new_config.category;
-            self.triggers = new_config.triggers;
-
-            Ok(())
+    impl Fallback {
+        /// 回退使用的文本格式，不回退时为 `None`
+        pub fn text_format(self) -> Option<TextFormat> {
+            match self {
+                Fallback::None => None,
+                Fallback::Text => Some(TextFormat::Plain),
+                Fallback::Markdown => Some(TextFormat::Markdown),
+            }
         }
+    }
 
-        /// 计算配置哈希用于缓存
-        pub fn content_hash(&self) -> u64 {
-            use std::collections::hash_map::DefaultHasher;
-            let mut hasher = DefaultHasher::new();
-            self.title.hash(&mut hasher);
-            self.subtitle.hash(&mut hasher);
-            format!("{:?}", self.theme).hash(&mut hasher);
-            for cat in &self.category {
-                cat.hash(&mut hasher);
+    /// 长菜单分页设置
+    #[derive(Debug, Serialize, Deserialize, Clone)]
+    pub struct Pagination {
+        /// 单页估算高度上限（CSS 像素），0 表示不限
+        #[serde(default = "default_max_height")]
+        pub max_height: u32,
+        /// 单页插件数上限，0 表示不限
+        #[serde(default)]
+        pub max_plugins: usize,
+        /// 多页菜单的发送方式
+        #[serde(default)]
+        pub send: PageSend,
+    }
+
+    fn default_max_height() -> u32 {
+        2400
+    }
+
+    impl Default for Pagination {
+        fn default() -> Self {
+            Self {
+                max_height: default_max_height(),
+                max_plugins: 0,
+                send: PageSend::default(),
             }
-            hasher.finish()
         }
+    }
 
-        /// 搜索指令/插件
-        pub fn search(&self, keyword: &str) -> Vec<SearchResult> {
-            let kw = keyword.to_lowercase();
-            let mut results = Vec::new();
+    /// 预渲染设置：启动及重载成功后在后台提前生成图片
+    #[derive(Debug, Serialize, Deserialize, Clone)]
+    pub struct Prerender {
+        /// 是否预渲染主菜单（全部分页）
+        #[serde(default = "default_prerender_enabled")]
+        pub enabled: bool,
+        /// 同时预渲染每个分类页
+        #[serde(default)]
+        pub categories: bool,
+        /// 同时预渲染各群聊/私聊覆盖的变体
+        #[serde(default)]
+        pub variants: bool,
+    }
 
-            for cat in &self.category {
-                for plugin in &cat.plugins {
-                    // 匹配插件名
-                    if plugin.name.to_lowercase().contains(&kw) {
-                        results.push(SearchResult {
-                            category: cat.name.clone(),
-                            plugin: plugin.name.clone(),
-                            desc: plugin.desc.clone(),
-                            matched_cmd: None,
-                        });
-                        continue;
-                    }
-                    // 匹配指令
-                    for cmd in &plugin.commands {
-                        if cmd.to_lowercase().contains(&kw) {
-                            results.push(SearchResult {
-                                category: cat.name.clone(),
-                                plugin: plugin.name.clone(),
-                                desc: plugin.desc.clone(),
-                                matched_cmd: Some(cmd.clone()),
-                            });
-                            break;
-                        }
-                    }
-                }
+    fn default_prerender_enabled() -> bool {
+        true
+    }
+
+    impl Default for Prerender {
+        fn default() -> Self {
+            Self {
+                enabled: default_prerender_enabled(),
+                categories: false,
+                variants: false,
             }
-            results
         }
+    }
 
-        /// 获取分类列表
-        pub fn category_names(&self) -> Vec<String> {
-            self.category.iter().map(|c| c.name.clone()).collect()
+    /// 图片发送设置
+    #[derive(Debug, Serialize, Deserialize, Clone)]
+    pub struct ImageSetting {
+        /// 图片地址的形式
+        #[serde(default)]
+        pub send: ImageSend,
+        /// `send = "http"` 时缓存目录对外提供访问的地址
+        #[serde(default)]
+        pub base_url: String,
+        /// `send = "base64"` 时内存中保留的图片数量
+        #[serde(default = "default_memory_cache")]
+        pub memory_cache: usize,
+    }
+
+    fn default_memory_cache() -> usize {
+        32
+    }
+
+    impl Default for ImageSetting {
+        fn default() -> Self {
+            Self {
+                send: ImageSend::default(),
+                base_url: String::new(),
+                memory_cache: default_memory_cache(),
+            }
         }
     }
 
-    #[derive(Debug, Clone)]
-    pub struct SearchResult {
-        pub category: String,
-        pub plugin: String,
-        pub desc: String,
-        pub matched_cmd: Option<String>,
+    /// 渲染设置
+    #[derive(Debug, Serialize, Deserialize, Clone)]
+    pub struct RenderSetting {
+        /// 视口宽度（CSS 像素）
+        #[serde(default = "default_render_width")]
+        pub width: u32,
+        /// 设备像素比，图片实际宽度为 `width × scale`
+        #[serde(default = "default_render_scale")]
+        pub scale: f64,
+        /// 图片格式
+        #[serde(default)]
+        pub format: ImageFormat,
+        /// 图片质量（1~100），PNG 格式忽略此项
+        #[serde(default = "default_render_quality")]
+        pub quality: u8,
+        /// 菜单布局
+        #[serde(default)]
+        pub layout: Layout,
+        /// 单次渲染（含启动浏览器）的超时时间，单位秒
+        #[serde(default = "default_render_timeout")]
+        pub timeout: u64,
+        /// 渲染失败后的重试次数
+        #[serde(default = "default_render_retries")]
+        pub retries: u32,
+        /// 连续失败达到该次数后暂停渲染、直接回退为文本，0 表示不熔断
+        #[serde(default = "default_breaker_threshold")]
+        pub breaker_threshold: u32,
+        /// 熔断持续时间，单位秒
+        #[serde(default = "default_breaker_cooldown")]
+        pub breaker_cooldown: u64,
     }
-}
 
-// ============================================================================
-//                              渲染模块
-// ============================================================================
-mod render {
-    use super::config::Config;
-    use anyhow::Result;
-    use cdp_html_shot::{Browser, CaptureOptions, Viewport};
-    use kovi::tokio;
-    use std::path::Path;
-    use tera::{Context, Tera};
+    fn default_render_width() -> u32 {
+        480
+    }
 
-    /// 现代化玻璃拟态风格模板 - 移动端优化版
-    /// 宽度调整为 480px，单栏布局
-    const HTML_TEMPLATE: &str = r##"
-<!DOCTYPE html>
-<html lang="zh-CN">
-<head>
-    <meta charset="UTF-8">
-    <style>
-        * { margin: 0; padding: 0; box-sizing: border-box; }
+    fn default_render_scale() -> f64 {
+        2.0
+    }
 
-        :root {
-            --primary: {{ theme.primary }};
-            --bg-start: {{ theme.bg_start }};
-            --bg-end: {{ theme.bg_end }};
-            --card-opacity: {{ theme.card_opacity }};
-        }
+    fn default_render_quality() -> u8 {
+        90
+    }
 
-        body {
-            font-family: 'HarmonyOS Sans SC', 'PingFang SC', 'Microsoft YaHei', sans-serif;
-            background: linear-gradient(135deg, var(--bg-start) 0%, var(--bg-end) 100%);
-            min-height: 100vh;
-            /* 宽度调整为手机逻辑像素宽度，生成图片后更适合手机查看 */
-            padding: 24px;
-            width: 480px;
-        }
+    fn default_render_timeout() -> u64 {
+        30
+    }
 
-        .container {
-            display: flex;
-            flex-direction: column;
-            gap: 20px;
-        }
+    fn default_render_retries() -> u32 {
+        2
+    }
 
-        /* 头部区域 */
-        .header {
-            text-align: center;
-            padding: 20px 0;
-            position: relative;
-        }
+    fn default_breaker_threshold() -> u32 {
+        3
+    }
 
-        .header::before {
-            content: '';
-            position: absolute;
-            top: 50%;
-            left: 50%;
-            transform: translate(-50%, -50%);
-            width: 150px;
-            height: 150px;
-            background: radial-gradient(circle, var(--primary) 0%, transparent 70%);
-            opacity: 0.15;
-            border-radius: 50%;
-            z-index: 0;
-        }
+    fn default_breaker_cooldown() -> u64 {
+        60
+    }
 
-        .title {
-            font-size: 32px;
-            font-weight: 800;
-            background: linear-gradient(135deg, var(--primary) 0%, #a855f7 100%);
-            -webkit-background-clip: text;
-            -webkit-text-fill-color: transparent;
-            background-clip: text;
-            position: relative;
-            z-index: 1;
-            letter-spacing: 1px;
+    impl Default for RenderSetting {
+        fn default() -> Self {
+            Self {
+                width: default_render_width(),
+                scale: default_render_scale(),
+                format: ImageFormat::default(),
+                quality: default_render_quality(),
+                layout: Layout::default(),
+                timeout: default_render_timeout(),
+                retries: default_render_retries(),
+                breaker_threshold: default_breaker_threshold(),
+                breaker_cooldown: default_breaker_cooldown(),
+            }
         }
+    }
 
-        .subtitle {
-            font-size: 13px;
-            color: #64748b;
-            margin-top: 6px;
-            letter-spacing: 3px;
-            text-transform: uppercase;
-        }
+    /// 渲染输出的图片格式
+    #[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+    #[serde(rename_all = "lowercase")]
+    pub enum ImageFormat {
+        Png,
+        #[default]
+        Jpeg,
+        Webp,
+    }
 
-        /* 分类区域 */
-        .category-section {
-            background: rgba(255, 255, 255, var(--card-opacity));
-            backdrop-filter: blur(20px);
-            -webkit-backdrop-filter: blur(20px);
-            border-radius: 18px;
-            padding: 20px;
-            border: 1px solid rgba(255, 255, 255, 0.6);
-            box-shadow:
-                0 4px 20px rgba(0, 0, 0, 0.04),
-                inset 0 1px 0 rgba(255, 255, 255, 0.9);
+    impl ImageFormat {
+        /// 缓存文件扩展名
+        pub fn extension(self) -> &'static str {
+            match self {
+                ImageFormat::Png => "png",
+                ImageFormat::Jpeg => "jpg",
+                ImageFormat::Webp => "webp",
+            }
         }
+    }
 
-        .category-header {
-            display: flex;
-            align-items: center;
-            gap: 12px;
-            margin-bottom: 16px;
-            padding-bottom: 12px;
-            border-bottom: 2px solid rgba(99, 102, 241, 0.1);
+    /// 菜单布局
+    #[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+    #[serde(rename_all = "lowercase")]
+    pub enum Layout {
+        /// 单栏，适合手机竖屏阅读
+        #[default]
+        Mobile,
+        /// 宽度足够时分类按多栏网格排列
+        Desktop,
+    }
+
+    /// 发送图片时使用的地址形式
+    #[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+    #[serde(rename_all = "lowercase")]
+    pub enum ImageSend {
+        /// 本地文件 `file:///`，要求 OneBot 实现与 Kovi 共享文件系统
+        #[default]
+        File,
+        /// 图片内容编码为 `base64://`
+        Base64,
+        /// 拼接 `base_url` 与缓存文件名
+        Http,
+    }
+
+    /// 多页菜单的发送方式
+    #[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+    #[serde(rename_all = "lowercase")]
+    pub enum PageSend {
+        /// 每页一条图片消息
+        #[default]
+        Images,
+        /// 合并为一条转发消息
+        Forward,
+    }
+
+    /// 触发搜索的前缀，触发词不能以这些前缀开头
+    pub const SEARCH_PREFIXES: [&str; 4] = ["帮助 ", "help ", "搜索 ", "search "];
+
+    /// 最后可用配置副本的路径
+    fn last_good_path(path: &Path) -> PathBuf {
+        path.with_file_name("config.last-good.toml")
+    }
+
+    /// 保存已生效配置的原文，作为最后可用的副本
+    fn save_last_good(path: &Path, content: &str) {
+        if let Err(e) = std::fs::write(last_good_path(path), content) {
+            kovi::log::warn!("保存可用配置副本失败: {}", e);
         }
+    }
 
-        .category-icon {
-            font-size: 20px;
-            width: 38px;
-            height: 38px;
-            display: flex;
-            align-items: center;
-            justify-content: center;
-            background: linear-gradient(135deg, var(--primary) 0%, #a855f7 100%);
-            border-radius: 10px;
-            color: white;
-            box-shadow: 0 4px 10px rgba(99, 102, 241, 0.25);
+    fn default_title() -> String {
+        "📚 帮助中心".into()
+    }
+    fn default_footer() -> String {
+        "Powered by Kovi Framework".into()
+    }
+    fn default_watch_config() -> bool {
+        true
+    }
+    fn default_triggers() -> Vec<String> {
+        vec![
+            "help".into(),
+            "帮助".into(),
+            "菜单".into(),
+            "menu".into(),
+            "指令".into(),
+            "功能".into(),
+        ]
+    }
+
+    impl Config {
+        pub fn load(data_dir: &PathBuf) -> Arc<RwLock<Self>> {
+            if !data_dir.exists() {
+                std::fs::create_dir_all(data_dir).expect("Failed to create data directory");
+            }
+            let config_path = data_dir.join("config.toml");
+
+            let default: Config =
+                toml::from_str(DEFAULT_CONFIG).expect("Default config parse error");
+
+            let mut config = if config_path.exists() {
+                match Self::read_source(&config_path) {
+                    Ok((config, warnings, content)) => {
+                        for issue in &warnings {
+                            kovi::log::warn!("[help-center] {}", issue);
+                        }
+                        save_last_good(&config_path, &content);
+                        config
+                    }
+                    Err(report) => {
+                        kovi::log::warn!("配置校验失败，使用最后一次可用的配置:\n{}", report);
+                        Self::read_last_good(&config_path).unwrap_or_else(|| {
+                            kovi::log::warn!("没有可用的历史配置，使用默认配置");
+                            default
+                        })
+                    }
+                }
+            } else {
+                load_toml_data(default, config_path.clone()).unwrap_or_else(|e| {
+                    kovi::log::warn!("配置加载失败，使用默认配置: {}", e);
+                    toml::from_str(DEFAULT_CONFIG).unwrap()
+                })
+            };
+
+            config.config_path = config_path;
+            Arc::new(RwLock::new(config))
         }
 
-        .category-name {
-            font-size: 18px;
-            font-weight: 700;
-            color: #1e293b;
+        /// 重新加载配置，成功时返回校验警告
+        ///
+        /// 新配置生效后保存一份副本，作为下次启动失败时的最后可用配置。
+        pub fn reload(&mut self) -> Result<Vec<Issue>, String> {
+            let (new_config, warnings, content) = Self::read_source(&self.config_path)?;
+            self.apply(new_config);
+            save_last_good(&self.config_path, &content);
+            Ok(warnings)
         }
 
-        .category-count {
-            font-size: 11px;
-            color: #64748b;
-            background: rgba(241, 245, 249, 0.8);
-            padding: 4px 10px;
-            border-radius: 12px;
-            margin-left: auto;
-            font-weight: 500;
+        /// 读取、解析并校验配置文件，不修改当前配置，也不保存副本
+        ///
+        /// 失败时返回带行列号的错误报告。
+        pub fn read_file(path: &Path) -> Result<(Config, Vec<Issue>), String> {
+            Self::read_source(path).map(|(config, warnings, _)| (config, warnings))
         }
 
-        /* 插件列表 - 改为单栏垂直布局 */
-        .plugins-list {
-            display: flex;
-            flex-direction: column;
-            gap: 12px;
+        /// 同 [`Config::read_file`]，额外返回校验通过的原文
+        fn read_source(path: &Path) -> Result<(Config, Vec<Issue>, String), String> {
+            let content =
+                std::fs::read_to_string(path).map_err(|e| format!("读取配置失败: {}", e))?;
+            let (config, warnings) =
+                validate::parse(&content).map_err(|issues| validate::report(&issues))?;
+            Ok((config, warnings, content))
         }
 
-        .plugin-card {
-            background: rgba(255, 255, 255, 0.6);
-            border-radius: 14px;
-            padding: 14px 16px;
-            border: 1px solid rgba(255, 255, 255, 0.8);
+        /// 读取最后一次校验通过的配置副本
+        fn read_last_good(path: &Path) -> Option<Config> {
+            let content = std::fs::read_to_string(last_good_path(path)).ok()?;
+            validate::parse(&content).ok().map(|(config, _)| config)
         }
 
-        .plugin-header {
-            display: flex;
-            align-items: flex-start;
-            gap: 12px;
-            margin-bottom: 10px;
+        /// 用新解析的配置替换当前内容（保留配置文件路径）
+        pub fn apply(&mut self, new_config: Config) {
+            self.title = new_config.title;
+            self.subtitle = new_config.subtitle;
+            self.footer = new_config.footer;
+            self.theme = new_config.theme;
+            self.category = new_config.category;
+            self.triggers = new_config.triggers;
+            self.search_reply = new_config.search_reply;
+            self.menu_reply = new_config.menu_reply;
+            self.fallback = new_config.fallback;
+            self.admins = new_config.admins;
+            self.allow_group_admin = new_config.allow_group_admin;
+            self.groups = new_config.groups;
+            self.private = new_config.private;
+            self.watch_config = new_config.watch_config;
+            self.pagination = new_config.pagination;
+            self.prerender = new_config.prerender;
+            self.image = new_config.image;
+            self.render = new_config.render;
         }
 
-        .plugin-icon {
-            font-size: 18px;
-            margin-top: 1px;
-            filter: grayscale(0.2);
+        /// 合并运行时注册的条目，得到实际展示用的配置
+        ///
+        /// 配置文件中已存在的同名插件优先，注册表中的重复条目会被忽略。
+        pub fn merged(&self) -> Config {
+            let mut merged = self.clone();
+            for extra in HelpRegistry::snapshot() {
+                let plugins: Vec<PluginItem> = extra
+                    .plugins
+                    .into_iter()
+                    .filter(|p| !merged.has_plugin(&p.name))
+                    .collect();
+
+                match merged.category.iter_mut().find(|c| c.name == extra.name) {
+                    Some(cat) => {
+                        if cat.icon.is_empty() {
+                            cat.icon = extra.icon;
+                        }
+                        if cat.color.is_none() {
+                            cat.color = extra.color;
+                        }
+                        cat.plugins.extend(plugins);
+                    }
+                    None if !plugins.is_empty() => {
+                        merged.category.push(Category { plugins, ..extra })
+                    }
+                    None => {}
+                }
+            }
+            merged
         }
 
-        .plugin-info {
-            flex: 1;
-            min-width: 0;
+        /// 来源对应的覆盖配置及其缓存变体名
+        fn scope_override(&self, scope: Scope) -> Option<(&ScopeOverride, String)> {
+            match scope {
+                Scope::Group(id) => self
+                    .groups
+                    .get(&id.to_string())
+                    .map(|o| (o, format!("group{}", id))),
+                Scope::Private => self.private.as_ref().map(|o| (o, "private".to_string())),
+            }
         }
 
-        .plugin-name {
-            font-size: 15px;
-            font-weight: 600;
-            color: #334155;
-            line-height: 1.4;
+        /// 全局配置及每个群聊/私聊覆盖对普通成员可见的配置与变体名，全局配置在最前
+        pub fn variants(&self) -> Vec<(Config, String)> {
+            let mut variants = vec![(
+                self.merged().visible_to(Viewer::Member),
+                "default".to_string(),
+            )];
+            for id in self.groups.keys().filter_map(|k| k.parse::<i64>().ok()) {
+                variants.push(self.view(Scope::Group(id), Viewer::Member));
+            }
+            if self.private.is_some() {
+                variants.push(self.view(Scope::Private, Viewer::Member));
+            }
+            variants
         }
 
-        .plugin-desc {
-            font-size: 13px;
-            color: #64748b;
-            margin-top: 4px;
-            line-height: 1.4;
+        /// 指定来源与查看者可见的配置及缓存变体名
+        ///
+        /// 存在仅管理员可见的条目时，管理员使用独立的 `<变体>-admin` 缓存。
+        pub fn view(&self, scope: Scope, viewer: Viewer) -> (Config, String) {
+            let (cfg, variant) = self.resolve(scope);
+            let variant = match viewer {
+                Viewer::Admin if cfg.has_admin_only() => format!("{}-admin", variant),
+                _ => variant,
+            };
+            (cfg.visible_to(viewer), variant)
+        }
+
+        /// 按查看者过滤：移除隐藏条目，非管理员再移除仅管理员可见的条目
+        ///
+        /// 因过滤而变空的分类一并移除；停用分类下的插件标记为停用并沿用分类的停用原因。
+        pub fn visible_to(mut self, viewer: Viewer) -> Config {
+            let visible = |hidden: bool, admin_only: bool| {
+                !hidden && (viewer == Viewer::Admin || !admin_only)
+            };
+
+            self.category.retain_mut(|cat| {
+                if !visible(cat.hidden, cat.admin_only) {
+                    return false;
+                }
+                let before = cat.plugins.len();
+                cat.plugins.retain(|p| visible(p.hidden, p.admin_only));
+                if cat.disabled {
+                    for plugin in &mut cat.plugins {
+                        plugin.disabled = true;
+                        if plugin.disabled_reason.is_none() {
+                            plugin.disabled_reason = cat.disabled_reason.clone();
+                        }
+                    }
+                }
+                before == 0 || !cat.plugins.is_empty()
+            });
+            self
+        }
+
+        /// 是否存在仅管理员可见的分类或插件
+        fn has_admin_only(&self) -> bool {
+            self.category
+                .iter()
+                .any(|c| c.admin_only || c.plugins.iter().any(|p| p.admin_only))
+        }
+
+        /// 解析指定来源的生效配置（已合并注册表），同时返回缓存变体名
+        ///
+        /// 没有覆盖配置的来源共用 `default` 变体。结果未按查看者过滤，展示前应使用 [`Config::view`]。
+        pub fn resolve(&self, scope: Scope) -> (Config, String) {
+            let mut cfg = self.merged();
+            let Some((ov, variant)) = self.scope_override(scope) else {
+                return (cfg, "default".to_string());
+            };
+
+            if let Some(title) = &ov.title {
+                cfg.title = title.clone();
+            }
+            if ov.subtitle.is_some() {
+                cfg.subtitle = ov.subtitle.clone();
+            }
+            if let Some(footer) = &ov.footer {
+                cfg.footer = footer.clone();
+            }
+            if let Some(theme) = &ov.theme {
+                cfg.theme = theme.clone();
+            }
+            if let Some(triggers) = &ov.triggers {
+                cfg.triggers = triggers.clone();
+            }
+
+            // 与已有插件同名的条目会被校验拒绝；注册表插件不经过校验，这里同样跳过
+            for extra in &ov.category {
+                let plugins: Vec<PluginItem> = extra
+                    .plugins
+                    .iter()
+                    .filter(|p| !cfg.has_plugin(&p.name))
+                    .cloned()
+                    .collect();
+                match cfg.category.iter_mut().find(|c| c.name == extra.name) {
+                    Some(cat) => cat.plugins.extend(plugins),
+                    None if !plugins.is_empty() => cfg.category.push(Category {
+                        plugins,
+                        ..extra.clone()
+                    }),
+                    None => {}
+                }
+            }
+
+            cfg.category
+                .retain(|c| !ov.hide_categories.contains(&c.name));
+            if !ov.hide_plugins.is_empty() {
+                for cat in &mut cfg.category {
+                    cat.plugins.retain(|p| !ov.hide_plugins.contains(&p.name));
+                }
+                cfg.category.retain(|c| !c.plugins.is_empty());
+            }
+
+            (cfg, variant)
+        }
+
+        /// 指定来源生效的触发词
+        pub fn triggers_for(&self, scope: Scope) -> &[String] {
+            self.scope_override(scope)
+                .and_then(|(ov, _)| ov.triggers.as_deref())
+                .unwrap_or(&self.triggers)
+        }
+
+        fn has_plugin(&self, name: &str) -> bool {
+            self.category
+                .iter()
+                .any(|c| c.plugins.iter().any(|p| p.name == name))
+        }
+
+        /// 按分类名（不区分大小写）或分类列表中的序号（从 1 开始）查找分类
+        ///
+        /// 名称优先，因此名为纯数字（如「2024」）的分类也能按名称找到。
+        pub fn find_category(&self, keyword: &str) -> Option<&Category> {
+            let keyword = keyword.trim();
+            self.category
+                .iter()
+                .find(|c| c.name.trim().to_lowercase() == keyword.to_lowercase())
+                .or_else(|| {
+                    let index = keyword.parse::<usize>().ok()?;
+                    index.checked_sub(1).and_then(|i| self.category.get(i))
+                })
+        }
+
+        /// 按名称或别名精确查找插件
+        pub fn find_plugin(&self, keyword: &str) -> Option<(&Category, &PluginItem)> {
+            self.category.iter().find_map(|cat| {
+                cat.plugins
+                    .iter()
+                    .find(|p| p.is_named(keyword))
+                    .map(|p| (cat, p))
+            })
+        }
+
+        /// 搜索指令/插件，按相关度从高到低排序
+        ///
+        /// 支持模糊匹配（编辑距离）与拼音全拼/首字母匹配，
+        /// 覆盖名称、指令、标签、描述与分类名，字段权重依次递减；
+        /// 命中分类名时返回该分类下的全部插件。
+        pub fn search(&self, keyword: &str) -> Vec<SearchResult> {
+            let query = search::Query::new(keyword);
+            let mut results = Vec::new();
+
+            for cat in &self.category {
+                let cat_score = query.score(&cat.name);
+
+                for plugin in &cat.plugins {
+                    let mut best: Option<(f32, MatchField, Option<String>)> = None;
+                    let mut consider = |score: f32, field: MatchField, cmd: Option<String>| {
+                        let weighted = score * field.weight();
+                        if weighted > 0.0 && best.as_ref().is_none_or(|b| weighted > b.0) {
+                            best = Some((weighted, field, cmd));
+                        }
+                    };
+
+                    consider(query.score(&plugin.name), MatchField::Name, None);
+                    for cmd in &plugin.commands {
+                        let score = cmd
+                            .search_terms()
+                            .map(|term| query.score(term))
+                            .fold(0.0, f32::max);
+                        consider(score, MatchField::Command, Some(cmd.syntax()));
+                    }
+                    let tag_score = plugin
+                        .tags
+                        .iter()
+                        .map(|tag| query.score(tag))
+                        .fold(0.0, f32::max);
+                    consider(tag_score, MatchField::Tag, None);
+                    consider(query.score(&plugin.desc), MatchField::Desc, None);
+                    consider(cat_score, MatchField::Category, None);
+
+                    if let Some((score, matched_field, matched_cmd)) = best {
+                        results.push(SearchResult {
+                            category: cat.name.clone(),
+                            category_color: cat.color.clone(),
+                            plugin: plugin.name.clone(),
+                            icon: plugin.icon.clone(),
+                            desc: plugin.desc.clone(),
+                            matched_cmd,
+                            score,
+                            matched_field,
+                            disabled: plugin.disabled,
+                            disabled_reason: plugin.disabled_reason.clone(),
+                        });
+                    }
+                }
+            }
+
+            // 稳定排序：同分时保持配置顺序
+            results.sort_by(|a, b| b.score.total_cmp(&a.score));
+            results
+        }
+
+        /// 判断用户是否具有管理权限
+        ///
+        /// `role` 为群消息中发送者的群角色（`owner` / `admin` / `member`）。
+        pub fn is_admin(&self, user_id: i64, role: Option<&str>) -> bool {
+            self.admins.contains(&user_id)
+                || (self.allow_group_admin && matches!(role, Some("owner" | "admin")))
+        }
+
+        /// 获取分类列表
+        pub fn category_names(&self) -> Vec<String> {
+            self.category.iter().map(|c| c.name.clone()).collect()
+        }
+    }
+
+    /// 查看菜单的用户身份，决定仅管理员可见的条目是否展示
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum Viewer {
+        Member,
+        Admin,
+    }
+
+    #[derive(Debug, Clone, Serialize)]
+    pub struct SearchResult {
+        pub category: String,
+        /// 分类颜色
+        pub category_color: Option<String>,
+        pub plugin: String,
+        /// 插件图标
+        pub icon: Option<String>,
+        pub desc: String,
+        pub matched_cmd: Option<String>,
+        /// 相关度得分（已乘字段权重）
+        pub score: f32,
+        /// 命中的字段
+        pub matched_field: MatchField,
+        /// 插件已停用
+        pub disabled: bool,
+        /// 停用原因
+        pub disabled_reason: Option<String>,
+    }
+
+    /// 搜索命中的字段
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+    #[serde(rename_all = "snake_case")]
+    pub enum MatchField {
+        Name,
+        Command,
+        Tag,
+        Desc,
+        Category,
+    }
+
+    impl MatchField {
+        /// 字段权重
+        pub fn weight(self) -> f32 {
+            match self {
+                MatchField::Name => 1.0,
+                MatchField::Command => 0.8,
+                MatchField::Tag => 0.6,
+                MatchField::Desc => 0.5,
+                MatchField::Category => 0.4,
+            }
+        }
+
+        pub fn label(self) -> &'static str {
+            match self {
+                MatchField::Name => "名称",
+                MatchField::Command => "指令",
+                MatchField::Tag => "标签",
+                MatchField::Desc => "描述",
+                MatchField::Category => "分类",
+            }
+        }
+    }
+
+    /// 测试用配置：默认配置中的分类替换为 `categories`
+    #[cfg(test)]
+    pub(crate) fn test_config(categories: Vec<Category>) -> Config {
+        let mut config: Config = toml::from_str(DEFAULT_CONFIG).unwrap();
+        config.category = categories;
+        config
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn config(names: &[&str]) -> Config {
+            test_config(names.iter().map(|n| Category::new(*n)).collect())
+        }
+
+        fn found(config: &Config, keyword: &str) -> Option<String> {
+            config.find_category(keyword).map(|c| c.name.clone())
+        }
+
+        #[test]
+        fn finds_category_by_name_then_index() {
+            let config = config(&["工具", "2024", "Games"]);
+            assert_eq!(found(&config, " games "), Some("Games".into()));
+            assert_eq!(found(&config, "1"), Some("工具".into()));
+            assert_eq!(found(&config, "3"), Some("Games".into()));
+            assert_eq!(found(&config, "2024"), Some("2024".into()));
+            assert_eq!(found(&config, "0"), None);
+            assert_eq!(found(&config, "4"), None);
+            assert_eq!(found(&config, "游戏"), None);
+        }
+
+        /// 各分类及其插件名
+        fn layout(config: &Config) -> Vec<(&str, Vec<&str>)> {
+            config
+                .category
+                .iter()
+                .map(|c| {
+                    let plugins = c.plugins.iter().map(|p| p.name.as_str()).collect();
+                    (c.name.as_str(), plugins)
+                })
+                .collect()
+        }
+
+        fn scoped() -> Config {
+            let mut config = test_config(vec![
+                Category::new("工具")
+                    .with_plugin(PluginItem::new("签到", ""))
+                    .with_plugin(PluginItem::new("天气", "")),
+                Category::new("娱乐").with_plugin(PluginItem::new("骰子", "")),
+            ]);
+            config.groups.insert(
+                "123".into(),
+                ScopeOverride {
+                    title: Some("群帮助".into()),
+                    theme: Some(toml::from_str("preset = \"dark\"").unwrap()),
+                    hide_categories: vec!["娱乐".into()],
+                    hide_plugins: vec!["天气".into()],
+                    category: vec![
+                        Category::new("工具").with_plugin(PluginItem::new("群管", "")),
+                        Category::new("群专属").with_plugin(PluginItem::new("抽奖", "")),
+                    ],
+                    ..Default::default()
+                },
+            );
+            config.private = Some(ScopeOverride {
+                footer: Some("私聊".into()),
+                ..Default::default()
+            });
+            config
+        }
+
+        #[test]
+        fn resolve_applies_group_override() {
+            let config = scoped();
+            let (cfg, variant) = config.resolve(Scope::Group(123));
+            assert_eq!(variant, "group123");
+            assert_eq!(cfg.title, "群帮助");
+            assert_eq!(cfg.theme.preset.as_deref(), Some("dark"));
+            assert_eq!(cfg.footer, config.footer);
+            assert_eq!(
+                layout(&cfg),
+                [("工具", vec!["签到", "群管"]), ("群专属", vec!["抽奖"])]
+            );
+        }
+
+        #[test]
+        fn resolve_falls_back_to_global() {
+            let config = scoped();
+            let (cfg, variant) = config.resolve(Scope::Group(456));
+            assert_eq!(variant, "default");
+            assert_eq!(cfg.title, config.title);
+            assert_eq!(
+                layout(&cfg),
+                [("工具", vec!["签到", "天气"]), ("娱乐", vec!["骰子"])]
+            );
+
+            let (cfg, variant) = config.resolve(Scope::Private);
+            assert_eq!(variant, "private");
+            assert_eq!(cfg.footer, "私聊");
+            assert_eq!(layout(&cfg).len(), 2);
+
+            let names: Vec<String> = config.variants().into_iter().map(|(_, v)| v).collect();
+            assert_eq!(names, ["default", "group123", "private"]);
+        }
+
+        #[test]
+        fn resolve_skips_duplicate_group_plugins() {
+            let mut config = scoped();
+            let ov = config.groups.get_mut("123").unwrap();
+            ov.hide_categories.clear();
+            ov.hide_plugins.clear();
+            ov.category = vec![
+                Category::new("工具").with_plugin(PluginItem::new("签到", "群内版本")),
+                Category::new("重复").with_plugin(PluginItem::new("骰子", "")),
+            ];
+
+            let (cfg, _) = config.resolve(Scope::Group(123));
+            assert_eq!(
+                layout(&cfg),
+                [("工具", vec!["签到", "天气"]), ("娱乐", vec!["骰子"])]
+            );
+            assert_eq!(cfg.find_plugin("签到").unwrap().1.desc, "");
+        }
+
+        fn with_admin_entries() -> Config {
+            test_config(vec![
+                Category::new("工具")
+                    .with_plugin(PluginItem::new("签到", "每日签到"))
+                    .with_plugin(PluginItem::new("封禁", "封禁成员").with_admin_only())
+                    .with_plugin(PluginItem::new("调试", "内部调试").with_hidden()),
+                Category::new("管理")
+                    .with_admin_only()
+                    .with_plugin(PluginItem::new("公告", "发布群公告")),
+            ])
+        }
+
+        #[test]
+        fn members_never_see_admin_only_entries() {
+            let config = with_admin_entries();
+            let (member, _) = config.view(Scope::Private, Viewer::Member);
+
+            assert_eq!(layout(&member), [("工具", vec!["签到"])]);
+            assert!(member.find_plugin("封禁").is_none());
+            assert!(member.find_plugin("公告").is_none());
+            assert!(member.find_category("管理").is_none());
+            assert!(member.find_category("2").is_none());
+            assert!(member.search("封禁").is_empty());
+            assert!(member.search("公告").is_empty());
+            assert!(member.search("调试").is_empty());
+            assert_eq!(member.category_names(), ["工具"]);
+        }
+
+        #[test]
+        fn admins_see_admin_only_but_not_hidden() {
+            let config = with_admin_entries();
+            let (admin, _) = config.view(Scope::Private, Viewer::Admin);
+
+            assert_eq!(
+                layout(&admin),
+                [("工具", vec!["签到", "封禁"]), ("管理", vec!["公告"])]
+            );
+            assert!(admin.find_plugin("封禁").is_some());
+            assert!(admin.find_category("管理").is_some());
+            assert!(!admin.search("公告").is_empty());
+            assert!(admin.find_plugin("调试").is_none());
+        }
+
+        #[test]
+        fn admin_requires_listing_or_allowed_group_role() {
+            let mut config = test_config(Vec::new());
+            config.admins = vec![10001];
+            config.allow_group_admin = false;
+            assert!(config.is_admin(10001, None));
+            assert!(!config.is_admin(10002, Some("owner")));
+
+            config.allow_group_admin = true;
+            assert!(config.is_admin(10002, Some("owner")));
+            assert!(config.is_admin(10002, Some("admin")));
+            assert!(!config.is_admin(10002, Some("member")));
+            assert!(!config.is_admin(10002, None));
+        }
+
+        #[test]
+        fn admin_variants_cached_separately() {
+            let mut config = with_admin_entries();
+            config.groups.insert("123".into(), ScopeOverride::default());
+
+            let variant = |scope, viewer| config.view(scope, viewer).1;
+            assert_eq!(variant(Scope::Private, Viewer::Member), "default");
+            assert_eq!(variant(Scope::Private, Viewer::Admin), "default-admin");
+            assert_eq!(variant(Scope::Group(123), Viewer::Member), "group123");
+            assert_eq!(variant(Scope::Group(123), Viewer::Admin), "group123-admin");
+
+            // 没有仅管理员可见的条目时，管理员与成员共用缓存
+            let plain = test_config(vec![
+                Category::new("工具").with_plugin(PluginItem::new("签到", "")),
+            ]);
+            assert_eq!(plain.view(Scope::Private, Viewer::Admin).1, "default");
+        }
+
+        #[test]
+        fn numeric_name_wins_over_index() {
+            let config = config(&["工具", "游戏", "1"]);
+            assert_eq!(found(&config, "1"), Some("1".into()));
+            assert_eq!(found(&config, "2"), Some("游戏".into()));
+        }
+    }
+}
+
+// ============================================================================
+//                              配置校验
+// ============================================================================
+pub mod validate {
+    use super::config::{
+        self, Category, Config, ImageSend, ImageSetting, RenderSetting, SEARCH_PREFIXES,
+        ScopeOverride, Theme,
+    };
+    use kovi::toml;
+    use std::fmt;
+    use std::ops::Range;
+    use toml_edit::{ImDocument, Item, Table, TableLike, Value};
+
+    /// 报告中最多列出的问题数
+    const MAX_REPORTED: usize = 10;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum Severity {
+        /// 配置不可用，将被拒绝
+        Error,
+        /// 配置可用，但可能不符合预期
+        Warning,
+    }
+
+    /// 配置问题，附带在文件中的位置
+    #[derive(Debug, Clone)]
+    pub struct Issue {
+        pub severity: Severity,
+        /// 行号与列号（从 1 开始）
+        pub position: Option<(usize, usize)>,
+        /// 配置项路径，如 `category[0].plugins[1].name`
+        pub path: String,
+        pub message: String,
+    }
+
+    impl fmt::Display for Issue {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            let icon = match self.severity {
+                Severity::Error => "❌",
+                Severity::Warning => "⚠️",
+            };
+            write!(f, "{} ", icon)?;
+            if let Some((line, col)) = self.position {
+                write!(f, "第 {} 行第 {} 列 ", line, col)?;
+            }
+            if !self.path.is_empty() {
+                write!(f, "[{}] ", self.path)?;
+            }
+            write!(f, "{}", self.message)
+        }
+    }
+
+    /// 将问题列表格式化为多行报告
+    pub fn report(issues: &[Issue]) -> String {
+        let mut lines: Vec<String> = issues
+            .iter()
+            .take(MAX_REPORTED)
+            .map(ToString::to_string)
+            .collect();
+        if issues.len() > MAX_REPORTED {
+            lines.push(format!("...还有 {} 项问题", issues.len() - MAX_REPORTED));
+        }
+        lines.join("\n")
+    }
+
+    /// 解析并校验配置文本
+    ///
+    /// 没有错误时返回配置与警告列表，否则返回全部问题。
+    pub fn parse(content: &str) -> Result<(Config, Vec<Issue>), Vec<Issue>> {
+        let doc = ImDocument::parse(content).map_err(|e| {
+            vec![Issue {
+                severity: Severity::Error,
+                position: e.span().map(|span| position(content, span.start)),
+                path: String::new(),
+                message: format!("TOML 语法错误: {}", one_line(e.message())),
+            }]
+        })?;
+
+        let mut unknown = Vec::new();
+        let config: Config = serde_ignored::deserialize(toml::Deserializer::new(content), |path| {
+            unknown.push(segments(&path))
+        })
+        .map_err(|e| {
+            vec![Issue {
+                severity: Severity::Error,
+                position: e.span().map(|span| position(content, span.start)),
+                path: String::new(),
+                message: format!("配置格式错误: {}", one_line(e.message())),
+            }]
+        })?;
+
+        let mut checker = Checker {
+            content,
+            doc: &doc,
+            issues: Vec::new(),
+        };
+
+        for path in unknown {
+            checker.unknown(path);
+        }
+        checker.check_config(&config);
+
+        let mut issues = checker.issues;
+        issues.sort_by_key(|i| (i.severity != Severity::Error, i.position));
+        if issues.iter().any(|i| i.severity == Severity::Error) {
+            Err(issues)
+        } else {
+            Ok((config, issues))
+        }
+    }
+
+    /// 配置项路径片段
+    #[derive(Debug, Clone)]
+    enum Seg {
+        Key(String),
+        Index(usize),
+    }
+
+    fn key(k: &str) -> Seg {
+        Seg::Key(k.to_string())
+    }
+
+    /// 在父路径后追加片段
+    fn join(base: &[Seg], tail: impl IntoIterator<Item = Seg>) -> Vec<Seg> {
+        base.iter().cloned().chain(tail).collect()
+    }
+
+    fn segments(path: &serde_ignored::Path) -> Vec<Seg> {
+        use serde_ignored::Path;
+        match path {
+            Path::Root => Vec::new(),
+            Path::Seq { parent, index } => join(&segments(parent), [Seg::Index(*index)]),
+            Path::Map { parent, key } => join(&segments(parent), [Seg::Key(key.clone())]),
+            Path::Some { parent }
+            | Path::NewtypeStruct { parent }
+            | Path::NewtypeVariant { parent } => segments(parent),
+        }
+    }
+
+    fn display_path(path: &[Seg]) -> String {
+        let mut out = String::new();
+        for seg in path {
+            match seg {
+                Seg::Key(k) => {
+                    if !out.is_empty() {
+                        out.push('.');
+                    }
+                    out.push_str(k);
+                }
+                Seg::Index(i) => out.push_str(&format!("[{}]", i)),
+            }
+        }
+        out
+    }
+
+    fn one_line(message: &str) -> String {
+        message
+            .lines()
+            .map(str::trim)
+            .filter(|l| !l.is_empty())
+            .collect::<Vec<_>>()
+            .join("，")
+    }
+
+    /// 字节偏移转换为行号与列号
+    fn position(content: &str, offset: usize) -> (usize, usize) {
+        let before = &content[..offset.min(content.len())];
+        let line = before.matches('\n').count() + 1;
+        let col = before.rsplit('\n').next().map_or(0, |l| l.chars().count()) + 1;
+        (line, col)
+    }
+
+    /// 文档中的节点，用于按路径查找位置
+    enum Node<'a> {
+        Item(&'a Item),
+        Table(&'a Table),
+        Value(&'a Value),
+    }
+
+    impl<'a> Node<'a> {
+        fn span(&self) -> Option<Range<usize>> {
+            match self {
+                Node::Item(item) => item.span(),
+                Node::Table(table) => table.span(),
+                Node::Value(value) => value.span(),
+            }
+        }
+
+        fn table_like(&self) -> Option<&'a dyn TableLike> {
+            match self {
+                Node::Item(item) => item.as_table_like(),
+                Node::Table(table) => Some(*table),
+                Node::Value(value) => value.as_inline_table().map(|t| t as &dyn TableLike),
+            }
+        }
+
+        /// 子节点及其位置，`prefer_key` 时优先返回键名的位置
+        fn child(&self, seg: &Seg, prefer_key: bool) -> Option<(Node<'a>, Option<Range<usize>>)> {
+            match seg {
+                Seg::Key(k) => {
+                    let (key, item) = self.table_like()?.get_key_value(k)?;
+                    let span = if prefer_key {
+                        key.span().or_else(|| item.span())
+                    } else {
+                        item.span().or_else(|| key.span())
+                    };
+                    Some((Node::Item(item), span))
+                }
+                Seg::Index(i) => {
+                    let node = match self {
+                        Node::Item(Item::ArrayOfTables(tables)) => Node::Table(tables.get(*i)?),
+                        Node::Item(Item::Value(Value::Array(array)))
+                        | Node::Value(Value::Array(array)) => Node::Value(array.get(*i)?),
+                        _ => return None,
+                    };
+                    let span = node.span();
+                    Some((node, span))
+                }
+            }
+        }
+    }
+
+    struct Checker<'a> {
+        content: &'a str,
+        doc: &'a ImDocument<&'a str>,
+        issues: Vec<Issue>,
+    }
+
+    impl<'a> Checker<'a> {
+        /// 按路径查找文档中的节点
+        fn node(&self, path: &[Seg]) -> Option<Node<'a>> {
+            let mut node = Node::Table(self.doc.as_table());
+            for seg in path {
+                node = node.child(seg, false)?.0;
+            }
+            Some(node)
+        }
+
+        /// 查找路径在文件中的位置，找不到时退回最近的父节点
+        fn locate(&self, path: &[Seg], prefer_key: bool) -> Option<(usize, usize)> {
+            let mut node = Node::Table(self.doc.as_table());
+            let mut found = None;
+            for seg in path {
+                let Some((child, span)) = node.child(seg, prefer_key) else {
+                    break;
+                };
+                found = span.or(found);
+                node = child;
+            }
+            found.map(|span| position(self.content, span.start))
+        }
+
+        fn push(&mut self, severity: Severity, path: Vec<Seg>, message: String) {
+            // 未知配置项指向键名，其余问题指向取值
+            let prefer_key = severity == Severity::Warning;
+            self.issues.push(Issue {
+                severity,
+                position: self.locate(&path, prefer_key),
+                path: display_path(&path),
+                message,
+            });
+        }
+
+        fn error(&mut self, path: Vec<Seg>, message: String) {
+            self.push(Severity::Error, path, message);
+        }
+
+        fn warn(&mut self, path: Vec<Seg>, message: String) {
+            self.push(Severity::Warning, path, message);
+        }
+
+        fn unknown(&mut self, path: Vec<Seg>) {
+            let name = match path.last() {
+                Some(Seg::Key(k)) => k.clone(),
+                _ => String::new(),
+            };
+            self.warn(path, format!("未知配置项「{}」，已忽略", name));
+        }
+
+        /// 指令表及其参数表中的未知键
+        ///
+        /// 指令兼容字符串写法，经过无标签枚举反序列化，`serde_ignored` 看不到其中被忽略的键，
+        /// 因此直接在文档中检查。
+        fn check_command_keys(&mut self, path: &[Seg]) {
+            const COMMAND_KEYS: [&str; 5] = ["trigger", "args", "desc", "aliases", "example"];
+            const ARG_KEYS: [&str; 3] = ["name", "required", "type"];
+
+            let mut unknown = Vec::new();
+            let Some(command) = self.node(path).and_then(|n| n.table_like()) else {
+                return;
+            };
+            for (k, _) in command.iter() {
+                if !COMMAND_KEYS.contains(&k) {
+                    unknown.push(join(path, [key(k)]));
+                }
+            }
+            for i in 0.. {
+                let arg_path = join(path, [key("args"), Seg::Index(i)]);
+                let Some(arg) = self.node(&arg_path) else {
+                    break;
+                };
+                for (k, _) in arg.table_like().into_iter().flat_map(|t| t.iter()) {
+                    if !ARG_KEYS.contains(&k) {
+                        unknown.push(join(&arg_path, [key(k)]));
+                    }
+                }
+            }
+
+            for path in unknown {
+                self.unknown(path);
+            }
+        }
+
+        fn check_config(&mut self, config: &Config) {
+            self.check_theme(&config.theme, &[key("theme")]);
+            self.check_triggers(&config.triggers, &[key("triggers")]);
+            self.check_categories(&config.category, &[], &[]);
+            self.check_image(&config.image);
+            self.check_render(&config.render);
+
+            let global: Vec<&str> = config
+                .category
+                .iter()
+                .flat_map(|c| c.plugins.iter().map(|p| p.name.as_str()))
+                .collect();
+            for (id, ov) in &config.groups {
+                if id.parse::<i64>().is_err() {
+                    self.error(
+                        vec![key("groups"), key(id)],
+                        format!("「{}」不是有效的群号", id),
+                    );
+                }
+                self.check_override(ov, &[key("groups"), key(id)], &global);
+            }
+            if let Some(ov) = &config.private {
+                self.check_override(ov, &[key("private")], &global);
+            }
+        }
+
+        fn check_image(&mut self, image: &ImageSetting) {
+            let url = image.base_url.trim();
+            if image.send == ImageSend::Http && url.is_empty() {
+                self.error(
+                    vec![key("image"), key("send")],
+                    "使用 http 发送方式时必须填写 base_url".into(),
+                );
+            } else if !url.is_empty() && !url.starts_with("http://") && !url.starts_with("https://")
+            {
+                self.error(
+                    vec![key("image"), key("base_url")],
+                    format!("「{}」不是有效的 HTTP 地址", url),
+                );
+            }
+            if image.send == ImageSend::Base64 && image.memory_cache == 0 {
+                self.warn(
+                    vec![key("image"), key("memory_cache")],
+                    "内存缓存已关闭，每次发送都会从磁盘读取图片".into(),
+                );
+            }
+        }
+
+        fn check_render(&mut self, render: &RenderSetting) {
+            let path = |name: &str| vec![key("render"), key(name)];
+            if !(240..=4096).contains(&render.width) {
+                self.error(
+                    path("width"),
+                    format!("视口宽度应在 240 到 4096 之间，当前为 {}", render.width),
+                );
+            }
+            if !(0.5..=4.0).contains(&render.scale) {
+                self.error(
+                    path("scale"),
+                    format!("设备像素比应在 0.5 到 4 之间，当前为 {}", render.scale),
+                );
+            }
+            if !(1..=100).contains(&render.quality) {
+                self.error(
+                    path("quality"),
+                    format!("图片质量应在 1 到 100 之间，当前为 {}", render.quality),
+                );
+            }
+            if render.timeout == 0 {
+                self.error(path("timeout"), "渲染超时时间必须大于 0 秒".into());
+            }
+        }
+
+        /// `global` 为全局配置中的插件名，覆盖配置中的额外插件不能与之重名
+        fn check_override(&mut self, ov: &ScopeOverride, base: &[Seg], global: &[&str]) {
+            if let Some(theme) = &ov.theme {
+                self.check_theme(theme, &join(base, [key("theme")]));
+            }
+            if let Some(triggers) = &ov.triggers {
+                self.check_triggers(triggers, &join(base, [key("triggers")]));
+            }
+            self.check_categories(&ov.category, base, global);
+        }
+
+        fn check_theme(&mut self, theme: &Theme, base: &[Seg]) {
+            if let Some(name) = &theme.preset {
+                self.check_preset(name, join(base, [key("preset")]));
+            }
+            for (name, value) in theme.colors() {
+                if let Some(value) = value
+                    && !is_css_color(value)
+                {
+                    self.error(
+                        join(base, [key(name)]),
+                        format!("「{}」不是有效的 CSS 颜色", value),
+                    );
+                }
+            }
+            if let Some(opacity) = theme.card_opacity
+                && !(0.0..=1.0).contains(&opacity)
+            {
+                self.error(
+                    join(base, [key("card_opacity")]),
+                    format!("卡片透明度应在 0 到 1 之间，当前为 {}", opacity),
+                );
+            }
+            if let Some(night) = &theme.night {
+                let night_base = join(base, [key("night")]);
+                self.check_preset(&night.preset, join(&night_base, [key("preset")]));
+                for (name, hour) in [("start", night.start), ("end", night.end)] {
+                    if hour > 23 {
+                        self.error(
+                            join(&night_base, [key(name)]),
+                            format!("小时应在 0 到 23 之间，当前为 {}", hour),
+                        );
+                    }
+                }
+                if night.start == night.end {
+                    self.warn(
+                        night_base,
+                        "夜间时段的开始与结束相同，夜间模式不会生效".into(),
+                    );
+                }
+            }
+        }
+
+        fn check_preset(&mut self, name: &str, path: Vec<Seg>) {
+            if config::preset(name).is_none() {
+                let names: Vec<_> = config::preset_names().collect();
+                self.error(
+                    path,
+                    format!("未知的主题预设「{}」，可选：{}", name, names.join("、")),
+                );
+            }
+        }
+
+        fn check_triggers(&mut self, triggers: &[String], base: &[Seg]) {
+            if triggers.is_empty() {
+                self.error(base.to_vec(), "触发词列表为空，将无法打开帮助菜单".into());
+            }
+            for (i, trigger) in triggers.iter().enumerate() {
+                let path = join(base, [Seg::Index(i)]);
+                let lower = trigger.trim().to_lowercase();
+                if lower.is_empty() {
+                    self.error(path, "触发词不能为空".into());
+                } else if let Some(prefix) = SEARCH_PREFIXES.iter().find(|p| lower.starts_with(*p))
+                {
+                    self.error(
+                        path,
+                        format!(
+                            "触发词「{}」以搜索前缀「{}」开头，会被当作搜索",
+                            trigger,
+                            prefix.trim()
+                        ),
+                    );
+                }
+            }
+        }
+
+        /// `base` 为分类列表所在的表，分类位于 `base.category[i]`
+        /// `inherited` 为外层配置中已有的插件名，同名插件视为重复
+        fn check_categories(&mut self, categories: &[Category], base: &[Seg], inherited: &[&str]) {
+            let mut seen: Vec<&str> = Vec::new();
+
+            for (i, cat) in categories.iter().enumerate() {
+                let cat_path = join(base, [key("category"), Seg::Index(i)]);
+
+                if cat.name.trim().is_empty() {
+                    self.error(join(&cat_path, [key("name")]), "分类名不能为空".into());
+                }
+                if let Some(color) = &cat.color
+                    && !is_css_color(color)
+                {
+                    self.error(
+                        join(&cat_path, [key("color")]),
+                        format!("「{}」不是有效的 CSS 颜色", color),
+                    );
+                }
+                self.check_disabled(cat.disabled, cat.disabled_reason.as_deref(), &cat_path);
+
+                for (j, plugin) in cat.plugins.iter().enumerate() {
+                    let plugin_path = join(&cat_path, [key("plugins"), Seg::Index(j)]);
+
+                    if plugin.name.trim().is_empty() {
+                        self.error(join(&plugin_path, [key("name")]), "插件名不能为空".into());
+                    } else if seen.contains(&plugin.name.as_str()) {
+                        self.error(
+                            join(&plugin_path, [key("name")]),
+                            format!("插件名「{}」重复", plugin.name),
+                        );
+                    } else if inherited.contains(&plugin.name.as_str()) {
+                        self.error(
+                            join(&plugin_path, [key("name")]),
+                            format!("插件名「{}」与全局配置中的插件重复", plugin.name),
+                        );
+                    } else {
+                        seen.push(&plugin.name);
+                    }
+
+                    for (k, cmd) in plugin.commands.iter().enumerate() {
+                        let command_path = join(&plugin_path, [key("commands"), Seg::Index(k)]);
+                        self.check_command_keys(&command_path);
+                        if cmd.trigger.trim().is_empty() {
+                            self.error(command_path, "指令触发词不能为空".into());
+                        }
+                    }
+                    self.check_disabled(
+                        plugin.disabled,
+                        plugin.disabled_reason.as_deref(),
+                        &plugin_path,
+                    );
+                }
+            }
+        }
+
+        /// 填写了停用原因却未停用，多半是漏写了 `disabled = true`
+        fn check_disabled(&mut self, disabled: bool, reason: Option<&str>, base: &[Seg]) {
+            if !disabled && reason.is_some() {
+                self.warn(
+                    join(base, [key("disabled_reason")]),
+                    "填写了停用原因但未设置 disabled = true，条目仍会正常显示".into(),
+                );
+            }
+        }
+    }
+
+    /// 检查是否为常见的 CSS 颜色写法：十六进制、颜色函数或颜色名
+    fn is_css_color(value: &str) -> bool {
+        let value = value.trim().to_ascii_lowercase();
+
+        if let Some(hex) = value.strip_prefix('#') {
+            return matches!(hex.len(), 3 | 4 | 6 | 8)
+                && hex.chars().all(|c| c.is_ascii_hexdigit());
+        }
+
+        for func in [
+            "rgb(", "rgba(", "hsl(", "hsla(", "hwb(", "lab(", "lch(", "oklab(", "oklch(", "color(",
+        ] {
+            if let Some(args) = value.strip_prefix(func) {
+                return args.ends_with(')') && args.len() > 1;
+            }
+        }
+
+        CSS_COLOR_NAMES.contains(&value.as_str())
+    }
+
+    const CSS_COLOR_NAMES: &[&str] = &[
+        "transparent",
+        "currentcolor",
+        "aliceblue",
+        "antiquewhite",
+        "aqua",
+        "aquamarine",
+        "azure",
+        "beige",
+        "bisque",
+        "black",
+        "blanchedalmond",
+        "blue",
+        "blueviolet",
+        "brown",
+        "burlywood",
+        "cadetblue",
+        "chartreuse",
+        "chocolate",
+        "coral",
+        "cornflowerblue",
+        "cornsilk",
+        "crimson",
+        "cyan",
+        "darkblue",
+        "darkcyan",
+        "darkgoldenrod",
+        "darkgray",
+        "darkgreen",
+        "darkgrey",
+        "darkkhaki",
+        "darkmagenta",
+        "darkolivegreen",
+        "darkorange",
+        "darkorchid",
+        "darkred",
+        "darksalmon",
+        "darkseagreen",
+        "darkslateblue",
+        "darkslategray",
+        "darkslategrey",
+        "darkturquoise",
+        "darkviolet",
+        "deeppink",
+        "deepskyblue",
+        "dimgray",
+        "dimgrey",
+        "dodgerblue",
+        "firebrick",
+        "floralwhite",
+        "forestgreen",
+        "fuchsia",
+        "gainsboro",
+        "ghostwhite",
+        "gold",
+        "goldenrod",
+        "gray",
+        "green",
+        "greenyellow",
+        "grey",
+        "honeydew",
+        "hotpink",
+        "indianred",
+        "indigo",
+        "ivory",
+        "khaki",
+        "lavender",
+        "lavenderblush",
+        "lawngreen",
+        "lemonchiffon",
+        "lightblue",
+        "lightcoral",
+        "lightcyan",
+        "lightgoldenrodyellow",
+        "lightgray",
+        "lightgreen",
+        "lightgrey",
+        "lightpink",
+        "lightsalmon",
+        "lightseagreen",
+        "lightskyblue",
+        "lightslategray",
+        "lightslategrey",
+        "lightsteelblue",
+        "lightyellow",
+        "lime",
+        "limegreen",
+        "linen",
+        "magenta",
+        "maroon",
+        "mediumaquamarine",
+        "mediumblue",
+        "mediumorchid",
+        "mediumpurple",
+        "mediumseagreen",
+        "mediumslateblue",
+        "mediumspringgreen",
+        "mediumturquoise",
+        "mediumvioletred",
+        "midnightblue",
+        "mintcream",
+        "mistyrose",
+        "moccasin",
+        "navajowhite",
+        "navy",
+        "oldlace",
+        "olive",
+        "olivedrab",
+        "orange",
+        "orangered",
+        "orchid",
+        "palegoldenrod",
+        "palegreen",
+        "paleturquoise",
+        "palevioletred",
+        "papayawhip",
+        "peachpuff",
+        "peru",
+        "pink",
+        "plum",
+        "powderblue",
+        "purple",
+        "rebeccapurple",
+        "red",
+        "rosybrown",
+        "royalblue",
+        "saddlebrown",
+        "salmon",
+        "sandybrown",
+        "seagreen",
+        "seashell",
+        "sienna",
+        "silver",
+        "skyblue",
+        "slateblue",
+        "slategray",
+        "slategrey",
+        "snow",
+        "springgreen",
+        "steelblue",
+        "tan",
+        "teal",
+        "thistle",
+        "tomato",
+        "turquoise",
+        "violet",
+        "wheat",
+        "white",
+        "whitesmoke",
+        "yellow",
+        "yellowgreen",
+    ];
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn warnings(content: &str) -> Vec<Issue> {
+            parse(content)
+                .map(|(_, w)| w)
+                .unwrap_or_else(|e| panic!("{}", report(&e)))
+        }
+
+        fn errors(content: &str) -> Vec<Issue> {
+            match parse(content) {
+                Ok(_) => panic!("配置应校验失败"),
+                Err(issues) => issues,
+            }
+        }
+
+        #[test]
+        fn reports_syntax_error_position() {
+            let issues = errors("title = \"帮助\"\nfooter = \n");
+            assert_eq!(issues.len(), 1);
+            assert_eq!(issues[0].position.map(|p| p.0), Some(2));
+            assert!(issues[0].message.starts_with("TOML 语法错误"));
+        }
+
+        #[test]
+        fn points_errors_at_value() {
+            let content = "\
+[[category]]
+name = \"工具\"
+color = \"notacolor\"
+
+[[category.plugins]]
+name = \"签到\"
+desc = \"每日签到\"
+
+[[category.plugins]]
+name = \"签到\"
+desc = \"重复\"
+";
+            let issues = errors(content);
+            let color = issues
+                .iter()
+                .find(|i| i.path == "category[0].color")
+                .unwrap();
+            assert_eq!(color.position, Some((3, 9)));
+            let dup = issues
+                .iter()
+                .find(|i| i.path == "category[0].plugins[1].name")
+                .unwrap();
+            assert_eq!(dup.position, Some((10, 8)));
+        }
+
+        #[test]
+        fn warns_unknown_keys_at_key() {
+            let content = "\
+titel = \"帮助\"
+
+[[category]]
+name = \"工具\"
+  icn = \"🔧\"
+";
+            let issues = warnings(content);
+            assert_eq!(issues.len(), 2);
+            assert_eq!(issues[0].path, "titel");
+            assert_eq!(issues[0].position, Some((1, 1)));
+            assert_eq!(issues[1].path, "category[0].icn");
+            assert_eq!(issues[1].position, Some((5, 3)));
+            assert!(issues.iter().all(|i| i.severity == Severity::Warning));
+        }
+
+        #[test]
+        fn rejects_group_plugins_duplicating_global() {
+            let content = "\
+[[category]]
+name = \"工具\"
+
+[[category.plugins]]
+name = \"签到\"
+desc = \"每日签到\"
+
+[[groups.123.category]]
+name = \"群专属\"
+
+[[groups.123.category.plugins]]
+name = \"抽奖\"
+desc = \"\"
+
+[[groups.123.category.plugins]]
+name = \"签到\"
+desc = \"群内版本\"
+";
+            let issues = errors(content);
+            assert_eq!(issues.len(), 1);
+            assert_eq!(issues[0].path, "groups.123.category[0].plugins[1].name");
+            assert_eq!(issues[0].position, Some((16, 8)));
+        }
+
+        #[test]
+        fn warns_unknown_keys_in_commands() {
+            let content = "\
+[[category]]
+name = \"工具\"
+
+[[category.plugins]]
+name = \"签到\"
+desc = \"每日签到\"
+commands = [
+  \"签到 <天数> - 旧写法\",
+  { trigger = \"补签\", descr = \"拼错\", args = [{ name = \"日期\", optional = true }] },
+]
+
+[[category.plugins]]
+name = \"排行\"
+desc = \"积分排行\"
+
+[[category.plugins.commands]]
+trigger = \"排行\"
+alias = [\"榜单\"]
+
+[[category.plugins.commands.args]]
+name = \"页码\"
+kind = \"数字\"
+";
+            let issues = warnings(content);
+            let paths: Vec<&str> = issues.iter().map(|i| i.path.as_str()).collect();
+            assert_eq!(
+                paths,
+                [
+                    "category[0].plugins[0].commands[1].descr",
+                    "category[0].plugins[0].commands[1].args[0].optional",
+                    "category[0].plugins[1].commands[0].alias",
+                    "category[0].plugins[1].commands[0].args[0].kind",
+                ]
+            );
+            assert_eq!(issues[0].position, Some((9, 21)));
+            assert_eq!(issues[2].position, Some((18, 1)));
+            assert_eq!(issues[3].position, Some((22, 1)));
+        }
+    }
+}
+
+// ============================================================================
+//                              搜索匹配
+// ============================================================================
+mod search {
+    use pinyin::ToPinyin;
+
+    /// 各匹配方式的基础得分
+    const EXACT: f32 = 1.0;
+    const PREFIX: f32 = 0.9;
+    const CONTAINS: f32 = 0.75;
+    const PINYIN_FULL: f32 = 0.7;
+    const PINYIN_INITIALS: f32 = 0.6;
+    const FUZZY: f32 = 0.5;
+
+    /// 对拼音做模糊匹配所需的最短关键词长度
+    const PINYIN_FUZZY_MIN_LEN: usize = 5;
+
+    /// 预处理后的搜索关键词
+    pub struct Query {
+        text: String,
+        chars: Vec<char>,
+        /// 纯字母关键词才参与拼音匹配
+        alphabetic: bool,
+    }
+
+    impl Query {
+        pub fn new(keyword: &str) -> Self {
+            let text = normalize(keyword);
+            let alphabetic = !text.is_empty() && text.chars().all(|c| c.is_ascii_alphabetic());
+            Self {
+                chars: text.chars().collect(),
+                text,
+                alphabetic,
+            }
+        }
+
+        /// 计算关键词与文本的匹配得分，0 表示未命中
+        pub fn score(&self, target: &str) -> f32 {
+            if self.text.is_empty() {
+                return 0.0;
+            }
+            let target = normalize(target);
+            if target.is_empty() {
+                return 0.0;
+            }
+
+            let literal = literal_score(&self.text, &target);
+            if literal > 0.0 {
+                return literal;
+            }
+
+            let pinyin = if self.alphabetic {
+                Pinyin::of(&target)
+            } else {
+                None
+            };
+
+            if let Some(py) = &pinyin {
+                let score = (self.pinyin_full_score(py) * PINYIN_FULL)
+                    .max(literal_score(&self.text, &py.initials) * PINYIN_INITIALS);
+                if score > 0.0 {
+                    return score;
+                }
+            }
+
+            // 拼音音节短且相似度高，较短的关键词只对原文做模糊匹配
+            let mut candidates = vec![target];
+            if let Some(py) = pinyin
+                && self.chars.len() >= PINYIN_FUZZY_MIN_LEN
+            {
+                candidates.extend(py.syllable_suffixes());
+            }
+            self.fuzzy(&candidates)
+        }
+
+        /// 全拼匹配，只允许从音节边界开始，避免「ai」命中「pai」之类的误判
+        fn pinyin_full_score(&self, py: &Pinyin) -> f32 {
+            let mut suffixes = py.syllable_suffixes();
+            match suffixes.next() {
+                Some(full) if full == self.text => EXACT,
+                Some(full) if full.starts_with(&self.text) => PREFIX,
+                _ if suffixes.any(|rest| rest.starts_with(&self.text)) => CONTAINS,
+                _ => 0.0,
+            }
+        }
+
+        /// 编辑距离模糊匹配：与候选文本的开头片段比较，取最小距离
+        fn fuzzy(&self, candidates: &[String]) -> f32 {
+            let max_dist = match self.chars.len() {
+                0..=2 => return 0.0,
+                3..=5 => 1,
+                _ => 2,
+            };
+
+            let mut best = usize::MAX;
+            for candidate in candidates {
+                let chars: Vec<char> = candidate.chars().collect();
+                best = best.min(edit_distance(&self.chars, &chars));
+                // 允许关键词只匹配候选文本的开头部分（长度上下浮动一个字符）
+                for len in self.chars.len().saturating_sub(1)..=self.chars.len() + 1 {
+                    if len > 0 && len < chars.len() {
+                        best = best.min(edit_distance(&self.chars, &chars[..len]));
+                    }
+                }
+            }
+
+            if best > max_dist {
+                return 0.0;
+            }
+            FUZZY * (1.0 - best as f32 / self.chars.len() as f32)
+        }
+    }
+
+    /// 字面匹配：完全相等、前缀、包含
+    fn literal_score(kw: &str, target: &str) -> f32 {
+        if target == kw {
+            EXACT
+        } else if target.starts_with(kw) {
+            PREFIX
+        } else if target.contains(kw) {
+            CONTAINS
+        } else {
+            0.0
+        }
+    }
+
+    /// 客户端搜索用的索引文本：各段原文（小写、去空白）及其拼音全拼与首字母，以空格分隔
+    pub fn index_text<'a>(texts: impl IntoIterator<Item = &'a str>) -> String {
+        let mut terms = Vec::new();
+        for text in texts {
+            let text = normalize(text);
+            if text.is_empty() {
+                continue;
+            }
+            if let Some(py) = Pinyin::of(&text) {
+                terms.push(py.full);
+                terms.push(py.initials);
+            }
+            terms.push(text);
+        }
+        terms.join(" ")
+    }
+
+    /// 统一小写并去除空白
+    fn normalize(text: &str) -> String {
+        text.chars()
+            .filter(|c| !c.is_whitespace())
+            .flat_map(char::to_lowercase)
+            .collect()
+    }
+
+    /// 文本的拼音形式，非汉字字符按原样作为单独音节
+    struct Pinyin {
+        full: String,
+        initials: String,
+        /// 每个音节在 `full` 中的起始位置
+        boundaries: Vec<usize>,
+    }
+
+    impl Pinyin {
+        /// 不含汉字时返回 `None`
+        fn of(text: &str) -> Option<Self> {
+            let mut py = Pinyin {
+                full: String::new(),
+                initials: String::new(),
+                boundaries: Vec::new(),
+            };
+            let mut has_han = false;
+
+            for (c, syllable) in text.chars().zip(text.to_pinyin()) {
+                py.boundaries.push(py.full.len());
+                match syllable {
+                    Some(syllable) => {
+                        has_han = true;
+                        py.full.push_str(syllable.plain());
+                        py.initials.push_str(syllable.first_letter());
+                    }
+                    None => {
+                        py.full.push(c);
+                        py.initials.push(c);
+                    }
+                }
+            }
+            has_han.then_some(py)
+        }
+
+        /// 从每个音节开始的全拼后缀
+        fn syllable_suffixes(&self) -> impl Iterator<Item = String> + '_ {
+            self.boundaries.iter().map(|&i| self.full[i..].to_string())
+        }
+    }
+
+    /// 编辑距离（相邻字符交换计为一次编辑）
+    fn edit_distance(a: &[char], b: &[char]) -> usize {
+        let mut dp = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+        for (i, row) in dp.iter_mut().enumerate() {
+            row[0] = i;
+        }
+        for (j, cell) in dp[0].iter_mut().enumerate() {
+            *cell = j;
+        }
+        for i in 1..=a.len() {
+            for j in 1..=b.len() {
+                let cost = usize::from(a[i - 1] != b[j - 1]);
+                dp[i][j] = (dp[i - 1][j] + 1)
+                    .min(dp[i][j - 1] + 1)
+                    .min(dp[i - 1][j - 1] + cost);
+                if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                    dp[i][j] = dp[i][j].min(dp[i - 2][j - 2] + 1);
+                }
+            }
+        }
+        dp[a.len()][b.len()]
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use crate::config::{Category, MatchField, PluginItem, test_config};
+
+        fn score(keyword: &str, target: &str) -> f32 {
+            Query::new(keyword).score(target)
+        }
+
+        #[test]
+        fn literal_matches_rank_exact_prefix_contains() {
+            assert_eq!(score("签到", "签到"), EXACT);
+            assert_eq!(score("签", "签到"), PREFIX);
+            assert_eq!(score("到", "签到"), CONTAINS);
+            assert_eq!(score("Sign In", "signin"), EXACT);
+            assert_eq!(score("", "签到"), 0.0);
+        }
+
+        #[test]
+        fn pinyin_full_and_initials() {
+            assert_eq!(score("qiandao", "签到"), PINYIN_FULL * EXACT);
+            assert_eq!(score("qian", "签到"), PINYIN_FULL * PREFIX);
+            assert_eq!(score("dao", "签到"), PINYIN_FULL * CONTAINS);
+            assert_eq!(score("qd", "签到"), PINYIN_INITIALS * EXACT);
+            assert_eq!(score("QD", "每日签到"), PINYIN_INITIALS * CONTAINS);
+            // 全拼只从音节边界开始匹配
+            assert_eq!(score("ai", "派"), 0.0);
+            // 含非字母的关键词不参与拼音匹配
+            assert_eq!(score("qd1", "签到"), 0.0);
+        }
+
+        #[test]
+        fn fuzzy_threshold_depends_on_length() {
+            // 两个字符以内不做模糊匹配
+            assert_eq!(score("hx", "help"), 0.0);
+            // 3~5 个字符允许 1 次编辑，相邻交换算一次
+            assert!(score("hepl", "help") > 0.0);
+            assert!(score("hlp", "help") > 0.0);
+            assert_eq!(score("hxlx", "help"), 0.0);
+            // 6 个字符以上允许 2 次编辑
+            assert!(score("wether", "weather") > 0.0);
+            assert!(score("wexthxr", "weather") > 0.0);
+            assert_eq!(score("wxxthxx", "weather"), 0.0);
+            // 只需匹配目标的开头部分
+            assert!(score("reminde", "reminder list") > 0.0);
+            assert!(score("hepl", "help") < PINYIN_INITIALS);
+        }
+
+        #[test]
+        fn pinyin_fuzzy_needs_longer_keyword() {
+            assert!(score("tianqii", "天气") > 0.0);
+            assert_eq!(score("tinqi", "天气"), FUZZY * 0.8);
+            assert_eq!(score("tanq", "天气"), 0.0);
+        }
+
+        #[test]
+        fn results_ranked_by_field_weight() {
+            let config = test_config(vec![
+                Category::new("工具")
+                    .with_plugin(PluginItem::new("描述命中", "天气"))
+                    .with_plugin(PluginItem::new("指令命中", "查询").with_command("天气 [城市]"))
+                    .with_plugin(PluginItem::new("天气", "名称命中"))
+                    .with_plugin(PluginItem::new("标签命中", "无关").with_tag("天气"))
+                    .with_plugin(PluginItem::new("未命中", "无关")),
+            ]);
+
+            let results = config.search("天气");
+            let order: Vec<(&str, MatchField)> = results
+                .iter()
+                .map(|r| (r.plugin.as_str(), r.matched_field))
+                .collect();
+            assert_eq!(
+                order,
+                [
+                    ("天气", MatchField::Name),
+                    ("指令命中", MatchField::Command),
+                    ("标签命中", MatchField::Tag),
+                    ("描述命中", MatchField::Desc),
+                ]
+            );
+            assert_eq!(results[1].matched_cmd.as_deref(), Some("天气 [城市]"));
+            assert!(results.windows(2).all(|w| w[0].score >= w[1].score));
+        }
+
+        #[test]
+        fn category_match_returns_all_plugins() {
+            let config = test_config(vec![
+                Category::new("娱乐")
+                    .with_plugin(PluginItem::new("今日运势", "抽签"))
+                    .with_plugin(PluginItem::new("猜数字", "小游戏")),
+                Category::new("工具").with_plugin(PluginItem::new("娱乐新闻", "资讯")),
+            ]);
+
+            let results = config.search("yule");
+            let plugins: Vec<&str> = results.iter().map(|r| r.plugin.as_str()).collect();
+            // 名称命中排在分类命中之前，分类下的插件按配置顺序排列
+            assert_eq!(plugins, ["娱乐新闻", "今日运势", "猜数字"]);
+            assert!(
+                results[1..]
+                    .iter()
+                    .all(|r| r.matched_field == MatchField::Category && r.category == "娱乐")
+            );
+        }
+
+        #[test]
+        fn index_text_includes_pinyin() {
+            assert_eq!(
+                index_text(["签到", "", "Daily Sign"]),
+                "qiandao qd 签到 dailysign"
+            );
+        }
+    }
+}
+
+// ============================================================================
+//                              运行时注册表
+// ============================================================================
+mod registry {
+    use super::config::{Category, PluginItem};
+    use std::sync::{OnceLock, RwLock};
+
+    static ENTRIES: OnceLock<RwLock<Vec<Category>>> = OnceLock::new();
+
+    fn entries() -> &'static RwLock<Vec<Category>> {
+        ENTRIES.get_or_init(|| RwLock::new(Vec::new()))
+    }
+
+    /// 运行时帮助注册表
+    ///
+    /// 其他插件可在启动时注册自己的帮助条目，与 `config.toml` 中的分类合并展示。
+    /// 注册或注销后配置哈希随之变化，缓存图片会自动刷新。
+    ///
+    /// ```ignore
+    /// use kovi_plugin_help_center::{HelpRegistry, PluginItem};
+    ///
+    /// HelpRegistry::register_plugin(
+    ///     "娱乐互动",
+    ///     PluginItem::new("今日运势", "查看今天的运气如何")
+    ///         .with_icon("🎲")
+    ///         .with_commands(["jrrp", "运势"]),
+    /// );
+    /// ```
+    pub struct HelpRegistry;
+
+    impl HelpRegistry {
+        /// 注册分类，同名分类会合并插件列表
+        pub fn register_category(category: Category) {
+            let mut entries = entries().write().unwrap();
+            match entries.iter_mut().find(|c| c.name == category.name) {
+                Some(existing) => {
+                    if !category.icon.is_empty() {
+                        existing.icon = category.icon;
+                    }
+                    if category.color.is_some() {
+                        existing.color = category.color;
+                    }
+                    for plugin in category.plugins {
+                        upsert(&mut existing.plugins, plugin);
+                    }
+                }
+                None => entries.push(category),
+            }
+        }
+
+        /// 向指定分类注册插件，分类不存在时自动创建
+        pub fn register_plugin(category: &str, plugin: PluginItem) {
+            let mut entries = entries().write().unwrap();
+            match entries.iter_mut().find(|c| c.name == category) {
+                Some(existing) => upsert(&mut existing.plugins, plugin),
+                None => entries.push(Category::new(category).with_plugin(plugin)),
+            }
+        }
+
+        /// 注销插件，返回是否存在该插件
+        pub fn unregister_plugin(name: &str) -> bool {
+            let mut entries = entries().write().unwrap();
+            let mut found = false;
+            for cat in entries.iter_mut() {
+                let before = cat.plugins.len();
+                cat.plugins.retain(|p| p.name != name);
+                found |= cat.plugins.len() != before;
+            }
+            entries.retain(|c| !c.plugins.is_empty());
+            found
+        }
+
+        /// 注销整个分类，返回是否存在该分类
+        pub fn unregister_category(name: &str) -> bool {
+            let mut entries = entries().write().unwrap();
+            let before = entries.len();
+            entries.retain(|c| c.name != name);
+            entries.len() != before
+        }
+
+        /// 当前已注册条目的快照
+        pub fn snapshot() -> Vec<Category> {
+            entries().read().unwrap().clone()
+        }
+    }
+
+    /// 同名插件覆盖，否则追加
+    fn upsert(plugins: &mut Vec<PluginItem>, plugin: PluginItem) {
+        match plugins.iter_mut().find(|p| p.name == plugin.name) {
+            Some(existing) => *existing = plugin,
+            None => plugins.push(plugin),
+        }
+    }
+}
+
+// ============================================================================
+//                              渲染模块
+// ============================================================================
+pub mod render {
+    use super::config::{self, Category, Config, Palette, PluginItem, RenderSetting, SearchResult};
+    use super::{cache, search};
+    use anyhow::Context as _;
+    use anyhow::Result;
+    use cdp_html_shot::{Browser, CaptureOptions, ImageFormat, Viewport};
+    use kovi::{log, tokio};
+    use serde::Serialize;
+    use std::collections::BTreeMap;
+    use std::path::{Path, PathBuf};
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::{Arc, Mutex, RwLock};
+    use std::time::{Duration, Instant};
+    use tera::{Context, Tera};
+    use tokio::sync::Mutex as AsyncMutex;
+
+    /// 公共样式：主题变量、页面背景、标题与页脚
+    const BASE_CSS: &str = r##"
+        * { margin: 0; padding: 0; box-sizing: border-box; }
+
+        :root {
+            --primary: {{ theme.primary | css }};
+            --bg-start: {{ theme.bg_start | css }};
+            --bg-end: {{ theme.bg_end | css }};
+            --card-opacity: {{ theme.card_opacity }};
+            --secondary: {{ theme.secondary | css }};
+            --surface: {{ theme.surface | css }};
+            --surface-alt: {{ theme.surface_alt | css }};
+            --text: {{ theme.text | css }};
+            --text-secondary: {{ theme.text_secondary | css }};
+            --text-muted: {{ theme.text_muted | css }};
+            --text-faint: {{ theme.text_faint | css }};
+            --on-primary: {{ theme.on_primary | css }};
+            --highlight: {{ theme.highlight | css }};
+            --shadow: {{ theme.shadow | css }};
+            --card-bg: color-mix(in srgb, var(--surface) calc(var(--card-opacity) * 100%), transparent);
+        }
+
+        body {
+            font-family: 'HarmonyOS Sans SC', 'PingFang SC', 'Microsoft YaHei', sans-serif;
+            background: linear-gradient(135deg, var(--bg-start) 0%, var(--bg-end) 100%);
+            min-height: 100vh;
+            /* 宽度取自 render.width，与渲染视口一致；默认 480px 为手机逻辑像素宽度 */
+            padding: 24px;
+            width: {{ render.width }}px;
+        }
+
+        .container {
+            display: flex;
+            flex-direction: column;
+            gap: 20px;
+        }
+
+        /* 头部区域 */
+        .header {
+            text-align: center;
+            padding: 20px 0;
+            position: relative;
+        }
+
+        .header::before {
+            content: '';
+            position: absolute;
+            top: 50%;
+            left: 50%;
+            transform: translate(-50%, -50%);
+            width: 150px;
+            height: 150px;
+            background: radial-gradient(circle, var(--primary) 0%, transparent 70%);
+            opacity: 0.15;
+            border-radius: 50%;
+            z-index: 0;
+        }
+
+        .title {
+            font-size: 32px;
+            font-weight: 800;
+            background: linear-gradient(135deg, var(--primary) 0%, var(--secondary) 100%);
+            -webkit-background-clip: text;
+            -webkit-text-fill-color: transparent;
+            background-clip: text;
+            position: relative;
+            z-index: 1;
+            letter-spacing: 1px;
+        }
+
+        .subtitle {
+            font-size: 13px;
+            color: var(--text-muted);
+            margin-top: 6px;
+            letter-spacing: 3px;
+            text-transform: uppercase;
+        }
+
+        /* 指令：触发词 + 参数标签 + 说明 */
+        .cmd-row {
+            display: flex;
+            flex-wrap: wrap;
+            align-items: center;
+            gap: 6px;
+        }
+
+        .cmd-tag {
+            font-family: 'JetBrains Mono', 'Fira Code', monospace;
+            font-size: 12px;
+            padding: 6px 10px;
+            background: var(--surface);
+            color: var(--primary);
+            border-radius: 8px;
+            font-weight: 600;
+            border: 1px solid color-mix(in srgb, var(--primary) 15%, transparent);
+            box-shadow: 0 2px 4px color-mix(in srgb, var(--shadow) 2%, transparent);
+        }
+
+        .arg-pill {
+            font-family: 'JetBrains Mono', 'Fira Code', monospace;
+            font-size: 11px;
+            padding: 4px 8px;
+            color: var(--text-secondary);
+            background: color-mix(in srgb, var(--surface-alt) 90%, transparent);
+            border: 1px solid color-mix(in srgb, var(--text-faint) 35%, transparent);
+            border-radius: 999px;
+        }
+
+        .arg-pill.optional {
+            color: var(--text-faint);
+            border-style: dashed;
+            background: transparent;
+        }
+
+        .arg-type {
+            color: var(--text-faint);
+            margin-left: 2px;
+        }
+
+        .cmd-desc {
+            font-size: 12px;
+            color: var(--text-muted);
+        }
+
+        /* 底部 */
+        .footer {
+            text-align: center;
+            padding: 16px 0 8px;
+            color: var(--text-faint);
+            font-size: 12px;
+        }
+
+        .footer-divider {
+            width: 40px;
+            height: 3px;
+            background: linear-gradient(90deg, transparent, var(--primary), transparent);
+            margin: 0 auto 12px;
+            border-radius: 2px;
+            opacity: 0.4;
+        }
+
+        .tip {
+            margin-top: 8px;
+            font-size: 11px;
+            color: color-mix(in srgb, var(--text-faint) 60%, transparent);
+            font-style: italic;
+        }
+
+        /* 已停用的条目：灰色显示并附带原因 */
+        .is-disabled {
+            opacity: 0.55;
+            filter: grayscale(1);
+        }
+
+        .disabled-badge {
+            display: inline-block;
+            margin-top: 6px;
+            padding: 2px 8px;
+            font-size: 11px;
+            font-weight: 500;
+            color: var(--text-muted);
+            background: color-mix(in srgb, var(--text-muted) 12%, transparent);
+            border-radius: 8px;
+        }
+
+        .category-header .disabled-badge {
+            margin-top: 0;
+        }
+"##;
+
+    /// 公共页脚
+    const FOOTER_PARTIAL: &str = r##"
+        <div class="footer">
+            <div class="footer-divider"></div>
+            <div>{{ footer }}</div>
+            <div class="tip">💡 发送「帮助 关键词」可搜索指令</div>
+        </div>
+"##;
+
+    /// 单条指令：触发词、参数标签与说明分开展示
+    const COMMAND_PARTIAL: &str = r##"
+<div class="cmd-row">
+    <span class="cmd-tag">{{ cmd.trigger }}</span>
+    {% for arg in cmd.args %}
+    <span class="arg-pill{% if not arg.required %} optional{% endif %}">
+        {%- if arg.required %}&lt;{{ arg.name }}&gt;{% else %}[{{ arg.name }}]{% endif -%}
+        {%- if arg.type %}<span class="arg-type">: {{ arg.type }}</span>{% endif -%}
+    </span>
+    {% endfor %}
+    {% if cmd.desc %}<span class="cmd-desc">{{ cmd.desc }}</span>{% endif %}
+</div>
+"##;
+
+    /// 现代化玻璃拟态风格模板
+    ///
+    /// 宽度取自 `render.width`；`render.layout` 为 `mobile` 时单栏排列，
+    /// 为 `desktop` 时分类按宽度排成多栏网格。
+    const HTML_TEMPLATE: &str = r##"
+<!DOCTYPE html>
+<html lang="zh-CN">
+<head>
+    <meta charset="UTF-8">
+    <style>
+        {% include "base.css" %}
+
+        .page-indicator {
+            display: inline-block;
+            margin-top: 10px;
+            padding: 3px 12px;
+            font-size: 12px;
+            color: var(--primary);
+            background: color-mix(in srgb, var(--primary) 10%, transparent);
+            border-radius: 999px;
+            position: relative;
+            z-index: 1;
+        }
+
+        /* 分类区域 */
+        .category-section {
+            background: var(--card-bg);
+            backdrop-filter: blur(20px);
+            -webkit-backdrop-filter: blur(20px);
+            border-radius: 18px;
+            padding: 20px;
+            border: 1px solid color-mix(in srgb, var(--surface) 60%, transparent);
+            box-shadow:
+                0 4px 20px color-mix(in srgb, var(--shadow) 4%, transparent),
+                inset 0 1px 0 color-mix(in srgb, var(--surface) 90%, transparent);
+        }
+
+        .category-header {
+            display: flex;
+            align-items: center;
+            gap: 12px;
+            margin-bottom: 16px;
+            padding-bottom: 12px;
+            border-bottom: 2px solid var(--accent-soft);
+        }
+
+        .category-icon {
+            font-size: 20px;
+            width: 38px;
+            height: 38px;
+            display: flex;
+            align-items: center;
+            justify-content: center;
+            background: linear-gradient(135deg, var(--accent) 0%, var(--accent-deep) 100%);
+            border-radius: 10px;
+            color: var(--accent-on);
+            box-shadow: 0 4px 10px color-mix(in srgb, var(--accent) 25%, transparent);
+        }
+
+        .category-name {
+            font-size: 18px;
+            font-weight: 700;
+            color: var(--text);
+        }
+
+        .category-continued {
+            font-size: 13px;
+            font-weight: 500;
+            color: var(--text-muted);
+        }
+
+        .category-count {
+            font-size: 11px;
+            color: var(--accent-text);
+            background: var(--accent-tint);
+            padding: 4px 10px;
+            border-radius: 12px;
+            margin-left: auto;
+            font-weight: 500;
+        }
+
+        /* 插件列表 - 改为单栏垂直布局 */
+        .plugins-list {
+            display: flex;
+            flex-direction: column;
+            gap: 12px;
+        }
+
+        .plugin-card {
+            background: color-mix(in srgb, var(--surface) 60%, transparent);
+            border-radius: 14px;
+            padding: 14px 16px;
+            border: 1px solid color-mix(in srgb, var(--surface) 80%, transparent);
+        }
+
+        .plugin-header {
+            display: flex;
+            align-items: flex-start;
+            gap: 12px;
+            margin-bottom: 10px;
+        }
+
+        .plugin-icon {
+            font-size: 18px;
+            margin-top: 1px;
+            filter: grayscale(0.2);
+        }
+
+        .plugin-info {
+            flex: 1;
+            min-width: 0;
+        }
+
+        .plugin-name {
+            font-size: 15px;
+            font-weight: 600;
+            color: var(--text-secondary);
+            line-height: 1.4;
+        }
+
+        .plugin-desc {
+            font-size: 13px;
+            color: var(--text-muted);
+            margin-top: 4px;
+            line-height: 1.4;
+        }
+
+        /* 指令列表 */
+        .commands {
+            display: flex;
+            flex-direction: column;
+            gap: 6px;
+            margin-left: 30px; /* 对齐文字内容 */
+        }
+
+        .category-section .cmd-tag {
+            color: var(--accent-text);
+            border-color: var(--accent-soft);
+        }
+
+        {% if render.layout == "desktop" %}
+        /* 桌面布局：宽度足够时分类按多栏网格排列 */
+        .container {
+            display: grid;
+            grid-template-columns: repeat(auto-fill, minmax(400px, 1fr));
+            align-items: start;
+        }
+
+        .header,
+        .footer {
+            grid-column: 1 / -1;
+        }
+        {% endif %}
+    </style>
+</head>
+<body>
+    <div class="container">
+        <div class="header">
+            <h1 class="title">{{ title }}</h1>
+            {% if subtitle %}
+            <div class="subtitle">{{ subtitle }}</div>
+            {% else %}
+            <div class="subtitle">Command Reference</div>
+            {% endif %}
+            {% if page and page.total > 1 %}
+            <div class="page-indicator">第 {{ page.number }} / {{ page.total }} 页</div>
+            {% endif %}
+        </div>
+
+        {% for cat in category %}
+        <div class="category-section" style="--accent: {{ cat.accent.base | css }}; --accent-deep: {{ cat.accent.deep | css }}; --accent-tint: {{ cat.accent.tint | css }}; --accent-soft: {{ cat.accent.soft | css }}; --accent-text: {{ cat.accent.text | css }}; --accent-on: {{ cat.accent.on | css }};">
+            <div class="category-header">
+                <div class="category-icon">{{ cat.icon | default(value="📦") }}</div>
+                <span class="category-name">{{ cat.name }}{% if loop.first and page and page.continued %}<span class="category-continued">（续）</span>{% endif %}</span>
+                {% if cat.disabled %}<span class="disabled-badge">已停用</span>{% endif %}
+                <span class="category-count">{{ cat.plugins | length }}</span>
+            </div>
+            <div class="plugins-list">
+                {% for plugin in cat.plugins %}
+                <div class="plugin-card{% if plugin.disabled %} is-disabled{% endif %}">
+                    <div class="plugin-header">
+                        <span class="plugin-icon">{{ plugin.icon | default(value="⚡") }}</span>
+                        <div class="plugin-info">
+                            <div class="plugin-name">{{ plugin.name }}</div>
+                            <div class="plugin-desc">{{ plugin.desc }}</div>
+                            {% if plugin.disabled %}
+                            <div class="disabled-badge">⛔ 已停用{% if plugin.disabled_reason %}：{{ plugin.disabled_reason }}{% endif %}</div>
+                            {% endif %}
+                        </div>
+                    </div>
+                    <div class="commands">
+                        {% for cmd in plugin.commands %}
+                        {% include "command.html" %}
+                        {% endfor %}
+                    </div>
+                </div>
+                {% endfor %}
+            </div>
+        </div>
+        {% endfor %}
+
+        {% include "footer.html" %}
+    </div>
+</body>
+</html>
+"##;
+
+    /// 网页导出模板：可折叠分类与客户端搜索，不依赖任何外部资源
+    ///
+    /// `fonts` 为嵌入的字体，排在字体列表最前，未提供时使用访问者的系统字体。
+    const EXPORT_TEMPLATE: &str = r##"
+<!DOCTYPE html>
+<html lang="zh-CN">
+<head>
+    <meta charset="UTF-8">
+    <meta name="viewport" content="width=device-width, initial-scale=1">
+    <title>{{ title | striptags }}</title>
+    <style>
+        {% include "base.css" %}
+        {% for font in fonts %}
+        @font-face {
+            font-family: "{{ font.family | css }}";
+            src: url({{ font.src | safe }}) format("{{ font.format | css }}");
+        }
+        {% endfor %}
+
+        body {
+            width: auto;
+            max-width: 760px;
+            margin: 0 auto;
+            {%- if fonts %}
+            font-family: {% for font in fonts %}"{{ font.family | css }}", {% endfor %}'HarmonyOS Sans SC', 'PingFang SC', 'Microsoft YaHei', sans-serif;
+            {%- endif %}
+        }
+
+        .search-box {
+            position: sticky;
+            top: 12px;
+            z-index: 2;
+        }
+
+        .search-input {
+            width: 100%;
+            padding: 12px 16px;
+            font-size: 15px;
+            color: var(--text);
+            background: var(--card-bg);
+            backdrop-filter: blur(20px);
+            -webkit-backdrop-filter: blur(20px);
+            border: 1px solid color-mix(in srgb, var(--primary) 25%, transparent);
+            border-radius: 14px;
+            outline: none;
+            box-shadow: 0 4px 20px color-mix(in srgb, var(--shadow) 6%, transparent);
+        }
+
+        .search-input:focus {
+            border-color: var(--primary);
+        }
+
+        .empty {
+            display: none;
+            text-align: center;
+            color: var(--text-muted);
+            font-size: 14px;
+        }
+
+        .category-section {
+            background: var(--card-bg);
+            backdrop-filter: blur(20px);
+            -webkit-backdrop-filter: blur(20px);
+            border-radius: 18px;
+            padding: 16px 20px;
+            border: 1px solid color-mix(in srgb, var(--surface) 60%, transparent);
+            box-shadow: 0 4px 20px color-mix(in srgb, var(--shadow) 4%, transparent);
+        }
+
+        .category-header {
+            display: flex;
+            align-items: center;
+            gap: 12px;
+            cursor: pointer;
+            list-style: none;
+            user-select: none;
+        }
+
+        .category-header::-webkit-details-marker {
+            display: none;
+        }
+
+        .category-section[open] .category-header {
+            margin-bottom: 16px;
+            padding-bottom: 12px;
+            border-bottom: 2px solid var(--accent-soft);
+        }
+
+        .category-icon {
+            font-size: 20px;
+            width: 38px;
+            height: 38px;
+            display: flex;
+            align-items: center;
+            justify-content: center;
+            background: linear-gradient(135deg, var(--accent) 0%, var(--accent-deep) 100%);
+            border-radius: 10px;
+            color: var(--accent-on);
+        }
+
+        .category-name {
+            font-size: 18px;
+            font-weight: 700;
+            color: var(--text);
+        }
+
+        .category-count {
+            font-size: 11px;
+            color: var(--accent-text);
+            background: var(--accent-tint);
+            padding: 4px 10px;
+            border-radius: 12px;
+            margin-left: auto;
+            font-weight: 500;
+        }
+
+        .category-arrow {
+            color: var(--text-faint);
+            transition: transform 0.2s;
+        }
+
+        .category-section[open] .category-arrow {
+            transform: rotate(90deg);
+        }
+
+        .plugins-list {
+            display: flex;
+            flex-direction: column;
+            gap: 12px;
+        }
+
+        .plugin-card {
+            background: color-mix(in srgb, var(--surface) 60%, transparent);
+            border-radius: 14px;
+            padding: 14px 16px;
+            border: 1px solid color-mix(in srgb, var(--surface) 80%, transparent);
+        }
+
+        .plugin-header {
+            display: flex;
+            align-items: flex-start;
+            gap: 12px;
+            margin-bottom: 10px;
+        }
+
+        .plugin-icon {
+            font-size: 18px;
+        }
+
+        .plugin-name {
+            font-size: 15px;
+            font-weight: 600;
+            color: var(--text-secondary);
+        }
+
+        .plugin-desc {
+            font-size: 13px;
+            color: var(--text-muted);
+            margin-top: 4px;
         }
 
-        /* 指令标签 */
         .commands {
+            display: flex;
+            flex-direction: column;
+            gap: 6px;
+            margin-left: 30px;
+        }
+
+        .category-section .cmd-tag {
+            color: var(--accent-text);
+            border-color: var(--accent-soft);
+        }
+
+        [hidden] {
+            display: none !important;
+        }
+    </style>
+</head>
+<body>
+    <div class="container">
+        <div class="header">
+            <h1 class="title">{{ title }}</h1>
+            <div class="subtitle">{{ subtitle | default(value="Command Reference") }}</div>
+        </div>
+
+        <div class="search-box">
+            <input class="search-input" id="search" type="search" placeholder="🔍 搜索插件、指令或拼音首字母" autocomplete="off">
+        </div>
+        <div class="empty" id="empty">未找到相关指令</div>
+
+        {% for cat in category %}
+        <details class="category-section" open data-keywords="{{ cat.keywords }}" style="--accent: {{ cat.accent.base | css }}; --accent-deep: {{ cat.accent.deep | css }}; --accent-tint: {{ cat.accent.tint | css }}; --accent-soft: {{ cat.accent.soft | css }}; --accent-text: {{ cat.accent.text | css }}; --accent-on: {{ cat.accent.on | css }};">
+            <summary class="category-header">
+                <div class="category-icon">{{ cat.icon | default(value="📦") }}</div>
+                <span class="category-name">{{ cat.name }}</span>
+                {% if cat.disabled %}<span class="disabled-badge">已停用</span>{% endif %}
+                <span class="category-count">{{ cat.plugins | length }}</span>
+                <span class="category-arrow">▶</span>
+            </summary>
+            <div class="plugins-list">
+                {% for plugin in cat.plugins %}
+                <div class="plugin-card{% if plugin.disabled %} is-disabled{% endif %}" data-keywords="{{ cat.plugin_keywords[loop.index0] }}">
+                    <div class="plugin-header">
+                        <span class="plugin-icon">{{ plugin.icon | default(value="⚡") }}</span>
+                        <div class="plugin-info">
+                            <div class="plugin-name">{{ plugin.name }}</div>
+                            <div class="plugin-desc">{{ plugin.desc }}</div>
+                            {% if plugin.disabled %}
+                            <div class="disabled-badge">⛔ 已停用{% if plugin.disabled_reason %}：{{ plugin.disabled_reason }}{% endif %}</div>
+                            {% endif %}
+                        </div>
+                    </div>
+                    <div class="commands">
+                        {% for cmd in plugin.commands %}
+                        {% include "command.html" %}
+                        {% endfor %}
+                    </div>
+                </div>
+                {% endfor %}
+            </div>
+        </details>
+        {% endfor %}
+
+        <div class="footer">
+            <div class="footer-divider"></div>
+            <div>{{ footer }}</div>
+        </div>
+    </div>
+    <script>
+        (function () {
+            var input = document.getElementById('search');
+            var empty = document.getElementById('empty');
+            var sections = document.querySelectorAll('.category-section');
+
+            input.addEventListener('input', function () {
+                var kw = input.value.toLowerCase().replace(/\s+/g, '');
+                var shown = 0;
+                sections.forEach(function (section) {
+                    var whole = !kw || section.dataset.keywords.indexOf(kw) >= 0;
+                    var count = 0;
+                    section.querySelectorAll('.plugin-card').forEach(function (card) {
+                        var hit = whole || card.dataset.keywords.indexOf(kw) >= 0;
+                        card.hidden = !hit;
+                        if (hit) count++;
+                    });
+                    section.hidden = count === 0;
+                    if (kw && count > 0) section.open = true;
+                    shown += count;
+                });
+                empty.style.display = shown === 0 ? 'block' : 'none';
+            });
+        })();
+    </script>
+</body>
+</html>
+"##;
+
+    /// 插件详情卡片模板
+    const DETAIL_TEMPLATE: &str = r##"
+<!DOCTYPE html>
+<html lang="zh-CN">
+<head>
+    <meta charset="UTF-8">
+    <style>
+        {% include "base.css" %}
+
+        .detail-card {
+            background: var(--card-bg);
+            backdrop-filter: blur(20px);
+            -webkit-backdrop-filter: blur(20px);
+            border-radius: 18px;
+            padding: 22px 20px;
+            border: 1px solid color-mix(in srgb, var(--surface) 60%, transparent);
+            box-shadow:
+                0 4px 20px color-mix(in srgb, var(--shadow) 4%, transparent),
+                inset 0 1px 0 color-mix(in srgb, var(--surface) 90%, transparent);
+            display: flex;
+            flex-direction: column;
+            gap: 18px;
+        }
+
+        .detail-head {
+            display: flex;
+            align-items: center;
+            gap: 14px;
+        }
+
+        .detail-icon {
+            font-size: 26px;
+            width: 52px;
+            height: 52px;
+            display: flex;
+            align-items: center;
+            justify-content: center;
+            background: linear-gradient(135deg, var(--primary) 0%, var(--secondary) 100%);
+            border-radius: 14px;
+            box-shadow: 0 4px 10px color-mix(in srgb, var(--primary) 25%, transparent);
+        }
+
+        .detail-name {
+            font-size: 22px;
+            font-weight: 800;
+            color: var(--text);
+        }
+
+        .detail-category {
+            font-size: 12px;
+            color: var(--text-muted);
+            margin-top: 4px;
+        }
+
+        .detail-desc {
+            font-size: 14px;
+            color: var(--text-secondary);
+            line-height: 1.6;
+        }
+
+        .section-title {
+            font-size: 13px;
+            font-weight: 700;
+            color: var(--primary);
+            letter-spacing: 1px;
+            margin-bottom: 8px;
+        }
+
+        .usage {
+            font-size: 13px;
+            color: var(--text-secondary);
+            line-height: 1.7;
+            white-space: pre-wrap;
+        }
+
+        .pills {
             display: flex;
             flex-wrap: wrap;
             gap: 6px;
-            margin-left: 30px; /* 对齐文字内容 */
         }
 
-        .cmd-tag {
-            font-family: 'JetBrains Mono', 'Fira Code', monospace;
-            font-size: 12px;
-            padding: 6px 10px;
-            background: white;
-            color: var(--primary);
-            border-radius: 8px;
-            font-weight: 600;
-            border: 1px solid rgba(99, 102, 241, 0.15);
-            box-shadow: 0 2px 4px rgba(0, 0, 0, 0.02);
+        .alias-tag {
+            font-family: 'JetBrains Mono', 'Fira Code', monospace;
+            font-size: 12px;
+            padding: 6px 10px;
+            background: var(--surface);
+            color: var(--text-muted);
+            border-radius: 8px;
+            font-weight: 500;
+            border: 1px solid color-mix(in srgb, var(--primary) 15%, transparent);
+        }
+
+        .tag-pill {
+            font-size: 11px;
+            padding: 3px 8px;
+            color: var(--primary);
+            background: color-mix(in srgb, var(--primary) 8%, transparent);
+            border-radius: 999px;
+        }
+
+        .command-list {
+            display: flex;
+            flex-direction: column;
+            gap: 8px;
+        }
+
+        .cmd-extra {
+            font-size: 12px;
+            color: var(--text-faint);
+            margin: 4px 0 0 2px;
+        }
+
+        .cmd-extra code {
+            font-family: 'JetBrains Mono', 'Fira Code', monospace;
+            color: var(--text-muted);
+        }
+
+        .param-row {
+            display: flex;
+            gap: 10px;
+            font-size: 13px;
+            padding: 6px 0;
+            border-bottom: 1px dashed color-mix(in srgb, var(--text-faint) 30%, transparent);
+        }
+
+        .param-row:last-child {
+            border-bottom: none;
+        }
+
+        .param-name {
+            font-family: 'JetBrains Mono', 'Fira Code', monospace;
+            font-weight: 600;
+            color: var(--text-secondary);
+            min-width: 72px;
+        }
+
+        .param-desc {
+            color: var(--text-muted);
+            flex: 1;
+        }
+
+        .example {
+            font-family: 'JetBrains Mono', 'Fira Code', monospace;
+            font-size: 12px;
+            color: var(--text-secondary);
+            background: color-mix(in srgb, var(--surface-alt) 80%, transparent);
+            border-radius: 8px;
+            padding: 8px 10px;
+            margin-bottom: 6px;
+            white-space: pre-wrap;
+        }
+
+        .notes {
+            font-size: 13px;
+            color: var(--text-muted);
+            line-height: 1.6;
+            padding-left: 18px;
+        }
+    </style>
+</head>
+<body>
+    <div class="container">
+        <div class="detail-card{% if plugin.disabled %} is-disabled{% endif %}">
+            <div class="detail-head">
+                <div class="detail-icon">{{ plugin.icon | default(value="⚡") }}</div>
+                <div>
+                    <div class="detail-name">{{ plugin.name }}</div>
+                    <div class="detail-category">{{ category.icon }} {{ category.name }}</div>
+                    {% if plugin.disabled %}
+                    <div class="disabled-badge">⛔ 已停用{% if plugin.disabled_reason %}：{{ plugin.disabled_reason }}{% endif %}</div>
+                    {% endif %}
+                </div>
+            </div>
+
+            <div class="detail-desc">{{ plugin.desc }}</div>
+
+            {% if plugin.tags %}
+            <div class="pills">
+                {% for tag in plugin.tags %}
+                <span class="tag-pill">#{{ tag }}</span>
+                {% endfor %}
+            </div>
+            {% endif %}
+
+            {% if plugin.usage %}
+            <div>
+                <div class="section-title">📖 用法</div>
+                <div class="usage">{{ plugin.usage }}</div>
+            </div>
+            {% endif %}
+
+            {% if plugin.commands %}
+            <div>
+                <div class="section-title">⌨️ 指令</div>
+                <div class="command-list">
+                    {% for cmd in plugin.commands %}
+                    <div>
+                        {% include "command.html" %}
+                        {% if cmd.aliases %}
+                        <div class="cmd-extra">别名：{{ cmd.aliases | join(sep=" / ") }}</div>
+                        {% endif %}
+                        {% if cmd.example %}
+                        <div class="cmd-extra">示例：<code>{{ cmd.example }}</code></div>
+                        {% endif %}
+                    </div>
+                    {% endfor %}
+                </div>
+            </div>
+            {% endif %}
+
+            {% if plugin.params %}
+            <div>
+                <div class="section-title">🧾 参数</div>
+                {% for param in plugin.params %}
+                <div class="param-row">
+                    <span class="param-name">{{ param.name }}</span>
+                    <span class="param-desc">{{ param.desc }}</span>
+                </div>
+                {% endfor %}
+            </div>
+            {% endif %}
+
+            {% if plugin.examples %}
+            <div>
+                <div class="section-title">💡 示例</div>
+                {% for example in plugin.examples %}
+                <div class="example">{{ example }}</div>
+                {% endfor %}
+            </div>
+            {% endif %}
+
+            {% if plugin.notes %}
+            <div>
+                <div class="section-title">⚠️ 注意事项</div>
+                <ul class="notes">
+                    {% for note in plugin.notes %}
+                    <li>{{ note }}</li>
+                    {% endfor %}
+                </ul>
+            </div>
+            {% endif %}
+
+            {% if plugin.aliases %}
+            <div>
+                <div class="section-title">🏷️ 别名</div>
+                <div class="pills">
+                    {% for alias in plugin.aliases %}
+                    <span class="alias-tag">{{ alias }}</span>
+                    {% endfor %}
+                </div>
+            </div>
+            {% endif %}
+        </div>
+
+        {% include "footer.html" %}
+    </div>
+</body>
+</html>
+"##;
+
+    /// 搜索结果模板
+    const SEARCH_TEMPLATE: &str = r##"
+<!DOCTYPE html>
+<html lang="zh-CN">
+<head>
+    <meta charset="UTF-8">
+    <style>
+        {% include "base.css" %}
+
+        .search-header {
+            text-align: center;
+            padding: 12px 0 4px;
+        }
+
+        .search-title {
+            font-size: 22px;
+            font-weight: 800;
+            color: var(--text);
+        }
+
+        .search-keyword {
+            color: var(--primary);
+        }
+
+        .search-count {
+            font-size: 12px;
+            color: var(--text-muted);
+            margin-top: 6px;
+        }
+
+        .result-card {
+            background: var(--card-bg);
+            backdrop-filter: blur(20px);
+            -webkit-backdrop-filter: blur(20px);
+            border-radius: 16px;
+            padding: 14px 16px 14px 18px;
+            border: 1px solid color-mix(in srgb, var(--surface) 60%, transparent);
+            border-left: 4px solid var(--accent);
+            box-shadow: 0 4px 20px color-mix(in srgb, var(--shadow) 4%, transparent);
+            display: flex;
+            gap: 12px;
+        }
+
+        .result-icon {
+            font-size: 22px;
+            line-height: 1.2;
+        }
+
+        .result-body {
+            flex: 1;
+            min-width: 0;
+            display: flex;
+            flex-direction: column;
+            gap: 6px;
+        }
+
+        .result-top {
+            display: flex;
+            align-items: center;
+            gap: 8px;
+        }
+
+        .result-name {
+            font-size: 15px;
+            font-weight: 700;
+            color: var(--text-secondary);
+        }
+
+        .result-category {
+            font-size: 11px;
+            color: var(--on-primary);
+            background: var(--accent);
+            padding: 2px 8px;
+            border-radius: 999px;
+        }
+
+        .result-field {
+            font-size: 11px;
+            color: var(--text-faint);
+            margin-left: auto;
+        }
+
+        .result-desc {
+            font-size: 13px;
+            color: var(--text-muted);
+            line-height: 1.4;
+        }
+
+        .result-cmd {
+            align-self: flex-start;
+        }
+
+        mark {
+            background: var(--highlight);
+            color: inherit;
+            border-radius: 3px;
+            padding: 0 1px;
+        }
+
+        .more {
+            text-align: center;
+            font-size: 12px;
+            color: var(--text-faint);
+        }
+    </style>
+</head>
+<body>
+    <div class="container">
+        <div class="search-header">
+            <div class="search-title">🔍 搜索「<span class="search-keyword">{{ keyword }}</span>」</div>
+            <div class="search-count">共找到 {{ total }} 条结果</div>
+        </div>
+
+        {% for item in items %}
+        <div class="result-card{% if item.result.disabled %} is-disabled{% endif %}" style="--accent: {{ item.result.category_color | default(value=theme.primary) | css }};">
+            <div class="result-icon">{{ item.result.icon | default(value="⚡") }}</div>
+            <div class="result-body">
+                <div class="result-top">
+                    <span class="result-name">{{ item.name | safe }}</span>
+                    <span class="result-category">{{ item.result.category }}</span>
+                    <span class="result-field">匹配{{ item.field }}</span>
+                </div>
+                {% if item.result.disabled %}
+                <div class="disabled-badge">⛔ 已停用{% if item.result.disabled_reason %}：{{ item.result.disabled_reason }}{% endif %}</div>
+                {% endif %}
+                <div class="result-desc">{{ item.desc | safe }}</div>
+                {% if item.cmd %}
+                <span class="cmd-tag result-cmd">🎯 {{ item.cmd | safe }}</span>
+                {% endif %}
+            </div>
+        </div>
+        {% endfor %}
+
+        {% if remaining > 0 %}
+        <div class="more">...还有 {{ remaining }} 条结果，请尝试更精确的关键词</div>
+        {% endif %}
+
+        {% include "footer.html" %}
+    </div>
+</body>
+</html>
+"##;
+
+    /// 搜索结果图片最多展示的条数
+    pub const SEARCH_IMAGE_LIMIT: usize = 10;
+
+    /// 搜索结果卡片，`name` / `desc` / `cmd` 为已转义并插入 `<mark>` 的 HTML
+    #[derive(Serialize)]
+    struct SearchItemView<'a> {
+        result: &'a SearchResult,
+        field: &'static str,
+        name: String,
+        desc: String,
+        cmd: Option<String>,
+    }
+
+    /// 按关键词（不区分大小写）切分文本，转义各片段后用 `<mark>` 包裹命中部分
+    fn highlight(text: &str, keyword: &str) -> String {
+        let fold = |c: char| c.to_lowercase().next().unwrap_or(c);
+        let chars: Vec<char> = text.chars().collect();
+        let kw: Vec<char> = keyword
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .map(fold)
+            .collect();
+
+        let mut html = String::new();
+        let mut plain = String::new();
+        let mut i = 0;
+        while i < chars.len() {
+            let hit = !kw.is_empty()
+                && i + kw.len() <= chars.len()
+                && chars[i..i + kw.len()]
+                    .iter()
+                    .map(|&c| fold(c))
+                    .eq(kw.iter().copied());
+            if hit {
+                html.push_str(&tera::escape_html(&std::mem::take(&mut plain)));
+                let matched: String = chars[i..i + kw.len()].iter().collect();
+                html.push_str(&format!("<mark>{}</mark>", tera::escape_html(&matched)));
+                i += kw.len();
+            } else {
+                plain.push(chars[i]);
+                i += 1;
+            }
+        }
+        html.push_str(&tera::escape_html(&plain));
+        html
+    }
+
+    /// 分类强调色及其派生色，均为可直接写入 CSS 的颜色值
+    #[derive(Serialize)]
+    struct Accent {
+        /// 原色：图标渐变起点
+        base: String,
+        /// 加深色：图标渐变终点
+        deep: String,
+        /// 与卡片底色混合的浅色：计数底色
+        tint: String,
+        /// 较浓的浅色：分隔线与标签边框
+        soft: String,
+        /// 在浅色与卡片上保证对比度的文字色
+        text: String,
+        /// 图标色块上的文字色
+        on: String,
+    }
+
+    /// 附带强调色的分类，模板中仍可按原字段访问
+    #[derive(Serialize)]
+    struct CategoryView<'a> {
+        #[serde(flatten)]
+        category: &'a Category,
+        accent: Accent,
+    }
+
+    type Rgb = [f32; 3];
+
+    const WHITE: Rgb = [255.0, 255.0, 255.0];
+    const BLACK: Rgb = [0.0, 0.0, 0.0];
+
+    /// 解析十六进制与 `rgb()` / `rgba()` 颜色，忽略透明度
+    fn parse_color(value: &str) -> Option<Rgb> {
+        let value = value.trim().to_ascii_lowercase();
+
+        if let Some(hex) = value.strip_prefix('#') {
+            let digits: Vec<u8> = hex
+                .chars()
+                .map(|c| c.to_digit(16).map(|d| d as u8))
+                .collect::<Option<_>>()?;
+            let channel = |i: usize| match digits.len() {
+                3 | 4 => Some((digits[i] * 17) as f32),
+                6 | 8 => Some((digits[i * 2] * 16 + digits[i * 2 + 1]) as f32),
+                _ => None,
+            };
+            return Some([channel(0)?, channel(1)?, channel(2)?]);
+        }
+
+        let args = value
+            .strip_prefix("rgba(")
+            .or_else(|| value.strip_prefix("rgb("))?
+            .strip_suffix(')')?;
+        let mut parts = args
+            .split(|c: char| c == ',' || c == '/' || c.is_whitespace())
+            .filter(|s| !s.is_empty());
+        let mut channel = || -> Option<f32> {
+            let part = parts.next()?;
+            let v = match part.strip_suffix('%') {
+                Some(pct) => pct.parse::<f32>().ok()? * 2.55,
+                None => part.parse().ok()?,
+            };
+            Some(v.clamp(0.0, 255.0))
+        };
+        Some([channel()?, channel()?, channel()?])
+    }
+
+    fn to_hex(c: Rgb) -> String {
+        format!(
+            "#{:02x}{:02x}{:02x}",
+            c[0].round() as u8,
+            c[1].round() as u8,
+            c[2].round() as u8
+        )
+    }
+
+    /// 按权重 `t`（0-1，`a` 的占比）混合两种颜色
+    fn mix(a: Rgb, b: Rgb, t: f32) -> Rgb {
+        [0, 1, 2].map(|i| a[i] * t + b[i] * (1.0 - t))
+    }
+
+    /// WCAG 相对亮度
+    fn luminance(c: Rgb) -> f32 {
+        let [r, g, b] = c.map(|v| {
+            let v = v / 255.0;
+            if v <= 0.03928 {
+                v / 12.92
+            } else {
+                ((v + 0.055) / 1.055).powf(2.4)
+            }
+        });
+        0.2126 * r + 0.7152 * g + 0.0722 * b
+    }
+
+    /// WCAG 对比度
+    fn contrast(a: Rgb, b: Rgb) -> f32 {
+        let (la, lb) = (luminance(a), luminance(b));
+        (la.max(lb) + 0.05) / (la.min(lb) + 0.05)
+    }
+
+    impl Accent {
+        fn new(color: &str, palette: &Palette) -> Self {
+            let (Some(c), Some(surface)) = (parse_color(color), parse_color(&palette.surface))
+            else {
+                return Self::css(color);
+            };
+
+            let tint = mix(c, surface, 0.14);
+
+            // 文字色逐步向黑（浅色背景）或白（深色背景）靠拢，直到对比度足够
+            let target = if luminance(surface) < 0.5 {
+                WHITE
+            } else {
+                BLACK
+            };
+            let mut text = c;
+            for _ in 0..12 {
+                if contrast(text, tint) >= 4.5 {
+                    break;
+                }
+                text = mix(text, target, 0.85);
+            }
+
+            let on = if contrast(WHITE, c) >= 3.0 {
+                WHITE
+            } else {
+                mix(c, BLACK, 0.2)
+            };
+
+            Self {
+                base: to_hex(c),
+                deep: to_hex(mix(c, BLACK, 0.8)),
+                tint: to_hex(tint),
+                soft: to_hex(mix(c, surface, 0.3)),
+                text: to_hex(text),
+                on: to_hex(on),
+            }
+        }
+
+        /// 无法解析的颜色（如颜色名、hsl）交给浏览器混合
+        fn css(color: &str) -> Self {
+            Self {
+                base: color.to_string(),
+                deep: format!("color-mix(in srgb, {} 80%, black)", color),
+                tint: format!("color-mix(in srgb, {} 14%, var(--surface))", color),
+                soft: format!("color-mix(in srgb, {} 30%, var(--surface))", color),
+                text: format!("color-mix(in srgb, {} 70%, var(--text))", color),
+                on: "var(--on-primary)".into(),
+            }
+        }
+    }
+
+    /// 为每个分类计算强调色，未设置颜色的分类使用主题主色
+    fn category_views<'a>(categories: &'a [Category], palette: &Palette) -> Vec<CategoryView<'a>> {
+        categories
+            .iter()
+            .map(|category| CategoryView {
+                category,
+                accent: Accent::new(
+                    category.color.as_deref().unwrap_or(&palette.primary),
+                    palette,
+                ),
+            })
+            .collect()
+    }
+
+    /// 菜单中的一页
+    #[derive(Serialize)]
+    pub struct Page {
+        /// 页码，从 1 开始
+        pub number: usize,
+        /// 总页数
+        pub total: usize,
+        /// 首个分类承接上一页
+        pub continued: bool,
+        #[serde(skip)]
+        pub categories: Vec<Category>,
+    }
+
+    /// 页面固定部分（边距、标题、页脚）的估算高度
+    const PAGE_CHROME_HEIGHT: u32 = 240;
+    /// 分类卡片自身（边距、分类标题）的估算高度
+    const CATEGORY_HEIGHT: u32 = 126;
+    /// 插件描述每行大约容纳的字符数
+    const DESC_CHARS_PER_LINE: usize = 26;
+    /// 桌面布局的最小栏宽，与模板中的 `minmax(400px, 1fr)` 一致
+    const GRID_COLUMN_WIDTH: u32 = 400;
+    /// 栏间距与页面左右边距，与公共样式一致
+    const GRID_GAP: u32 = 20;
+    const BODY_PADDING: u32 = 24;
+
+    /// 菜单的栏数：手机布局为单栏，桌面布局按视口宽度计算
+    fn grid_columns(settings: &RenderSetting) -> u32 {
+        match settings.layout {
+            config::Layout::Mobile => 1,
+            config::Layout::Desktop => {
+                let content = settings.width.saturating_sub(2 * BODY_PADDING);
+                ((content + GRID_GAP) / (GRID_COLUMN_WIDTH + GRID_GAP)).max(1)
+            }
+        }
+    }
+
+    /// 估算单个插件卡片的高度
+    fn plugin_height(plugin: &PluginItem) -> u32 {
+        let desc_lines = plugin
+            .desc
+            .chars()
+            .count()
+            .div_ceil(DESC_CHARS_PER_LINE)
+            .max(1);
+        70 + 18 * desc_lines as u32 + 36 * plugin.commands.len() as u32
+    }
+
+    /// 按分页设置把分类拆到多页，过长的分类会跨页延续
+    ///
+    /// 高度为估算值；每页至少包含一个插件，因此单个超高插件也会独占一页。
+    pub fn paginate(config: &Config) -> Vec<Page> {
+        let limits = &config.pagination;
+        // 多栏布局时内容分摊到各栏，可容纳的内容高度按栏数放大
+        let columns = grid_columns(&config.render);
+        let max_height = match limits.max_height {
+            0 => 0,
+            h => PAGE_CHROME_HEIGHT + h.saturating_sub(PAGE_CHROME_HEIGHT) * columns,
+        };
+        let over = |height: u32, plugins: usize| {
+            (max_height > 0 && height > max_height)
+                || (limits.max_plugins > 0 && plugins > limits.max_plugins)
+        };
+
+        let mut pages: Vec<(Vec<Category>, bool)> = Vec::new();
+        let mut current: Vec<Category> = Vec::new();
+        let mut continued = false;
+        let mut height = PAGE_CHROME_HEIGHT;
+        let mut plugins = 0;
+
+        for category in &config.category {
+            let mut part = Category {
+                plugins: Vec::new(),
+                ..category.clone()
+            };
+            height += CATEGORY_HEIGHT;
+
+            for plugin in &category.plugins {
+                let plugin_height = plugin_height(plugin);
+                if plugins > 0 && over(height + plugin_height, plugins + 1) {
+                    let split = !part.plugins.is_empty();
+                    if split {
+                        current.push(Category {
+                            plugins: std::mem::take(&mut part.plugins),
+                            ..part.clone()
+                        });
+                    }
+                    pages.push((std::mem::take(&mut current), continued));
+                    continued = split;
+                    height = PAGE_CHROME_HEIGHT + CATEGORY_HEIGHT;
+                    plugins = 0;
+                }
+                part.plugins.push(plugin.clone());
+                height += plugin_height;
+                plugins += 1;
+            }
+            current.push(part);
+        }
+        if !current.is_empty() || pages.is_empty() {
+            pages.push((current, continued));
+        }
+
+        let total = pages.len();
+        pages
+            .into_iter()
+            .enumerate()
+            .map(|(i, (categories, continued))| Page {
+                number: i + 1,
+                total,
+                continued,
+                categories,
+            })
+            .collect()
+    }
+
+    /// 内置模板，名称同时也是覆盖文件名（加 `.tera` 后缀）
+    const EMBEDDED_TEMPLATES: [(&str, &str); 7] = [
+        ("base.css", BASE_CSS),
+        ("footer.html", FOOTER_PARTIAL),
+        ("command.html", COMMAND_PARTIAL),
+        ("help.html", HTML_TEMPLATE),
+        ("detail.html", DETAIL_TEMPLATE),
+        ("search.html", SEARCH_TEMPLATE),
+        ("export.html", EXPORT_TEMPLATE),
+    ];
+
+    /// 当前生效的模板集合
+    static TEMPLATES: RwLock<Option<Arc<Tera>>> = RwLock::new(None);
+
+    /// 覆盖模板所在目录
+    pub fn templates_dir(data_dir: &Path) -> PathBuf {
+        data_dir.join("templates")
+    }
+
+    /// 加载模板：内置模板 + 数据目录 `templates/*.tera` 中的覆盖模板
+    ///
+    /// 与内置模板同名的文件（如 `help.html.tera`）会替换内置模板，
+    /// 其余文件可作为局部模板被 `include`。加载后用 `config` 试渲染主菜单，
+    /// 任何错误都会保留之前的模板。成功时返回覆盖文件数。
+    pub fn load_templates(data_dir: &Path, config: &Config) -> Result<usize> {
+        let mut sources: BTreeMap<String, String> = EMBEDDED_TEMPLATES
+            .iter()
+            .map(|(name, src)| (name.to_string(), src.to_string()))
+            .collect();
+
+        let mut overrides = BTreeMap::new();
+        if let Ok(entries) = std::fs::read_dir(templates_dir(data_dir)) {
+            for entry in entries.flatten() {
+                let file_name = entry.file_name().to_string_lossy().to_string();
+                let Some(name) = file_name.strip_suffix(".tera") else {
+                    continue;
+                };
+                let content = std::fs::read_to_string(entry.path())
+                    .with_context(|| format!("读取模板 {} 失败", file_name))?;
+                overrides.insert(name.to_string(), content);
+            }
+        }
+
+        let count = overrides.len();
+        sources.extend(overrides);
+
+        let mut tera = new_tera();
+        tera.add_raw_templates(sources.iter().map(|(n, s)| (n.as_str(), s.as_str())))?;
+        build_with(&tera, config, &paginate(config)[0])?;
+
+        *TEMPLATES.write().unwrap() = Some(Arc::new(tera));
+        Ok(count)
+    }
+
+    /// HTML 模板（含其引入的 `base.css`）自动转义插值，CSS 值通过 `css` 过滤器输出
+    fn new_tera() -> Tera {
+        let mut tera = Tera::default();
+        tera.autoescape_on(vec![".html"]);
+        tera.register_filter("css", CssValue);
+        tera
+    }
+
+    /// 模板过滤器 `css`：去掉可能跳出 CSS 声明或 `<style>` 的字符
+    ///
+    /// HTML 转义会把 `rgb(0 0 0 / 50%)` 中的 `/` 变成实体，在 `<style>` 中无法还原，
+    /// 因此颜色等 CSS 值改用此过滤器，输出不再转义。
+    struct CssValue;
+
+    impl tera::Filter for CssValue {
+        fn filter(
+            &self,
+            value: &tera::Value,
+            _: &std::collections::HashMap<String, tera::Value>,
+        ) -> tera::Result<tera::Value> {
+            let text = match value {
+                tera::Value::String(s) => s.clone(),
+                other => other.to_string(),
+            };
+            let cleaned = text
+                .chars()
+                .filter(|c| !matches!(c, '<' | '>' | '{' | '}' | ';' | '"' | '\'' | '\\'))
+                .collect::<String>();
+            Ok(tera::Value::String(cleaned))
+        }
+
+        fn is_safe(&self) -> bool {
+            true
+        }
+    }
+
+    fn templates() -> Result<Arc<Tera>> {
+        if let Some(t) = TEMPLATES.read().unwrap().as_ref() {
+            return Ok(t.clone());
+        }
+        let mut tera = new_tera();
+        tera.add_raw_templates(EMBEDDED_TEMPLATES)?;
+        let tera = Arc::new(tera);
+        *TEMPLATES.write().unwrap() = Some(tera.clone());
+        Ok(tera)
+    }
+
+    /// 生成 HTML
+    pub fn build_html(config: &Config, page: &Page) -> Result<String> {
+        let tera = templates()?;
+        build_with(&tera, config, page)
+    }
+
+    /// 生成单个分类的菜单 HTML，以分类名为标题、分类颜色为主色
+    pub fn build_category_html(config: &Config, category: &Category) -> Result<String> {
+        let mut palette = config.theme.palette();
+        if let Some(color) = &category.color {
+            palette.secondary = Accent::new(color, &palette).deep;
+            palette.primary = color.clone();
+        }
+
+        let mut view = config.clone();
+        view.title = format!("{} {}", category.icon, category.name)
+            .trim()
+            .to_string();
+        view.subtitle = Some(config.title.clone());
+
+        let page = Page {
+            number: 1,
+            total: 1,
+            continued: false,
+            categories: vec![category.clone()],
+        };
+        let tera = templates()?;
+        render_help(&tera, &view, &page, &palette)
+    }
+
+    fn build_with(tera: &Tera, config: &Config, page: &Page) -> Result<String> {
+        render_help(tera, config, page, &config.theme.palette())
+    }
+
+    fn render_help(tera: &Tera, config: &Config, page: &Page, palette: &Palette) -> Result<String> {
+        let mut ctx = Context::from_serialize(config)?;
+        ctx.insert("category", &category_views(&page.categories, palette));
+        ctx.insert("theme", palette);
+        ctx.insert("page", page);
+        Ok(tera.render("help.html", &ctx)?)
+    }
+
+    /// 网页导出中的分类：附带客户端搜索用的关键词索引
+    #[derive(Serialize)]
+    struct ExportCategoryView<'a> {
+        #[serde(flatten)]
+        view: CategoryView<'a>,
+        keywords: String,
+        /// 与 `plugins` 一一对应
+        plugin_keywords: Vec<String>,
+    }
+
+    /// 嵌入网页的字体，`src` 为 data URI
+    #[derive(Serialize)]
+    pub struct EmbeddedFont {
+        pub family: String,
+        /// CSS `format()` 名称，如 `woff2`、`truetype`
+        pub format: &'static str,
+        pub src: String,
+    }
+
+    /// 生成可独立打开的网页版菜单：样式内联、无外部资源，支持搜索与折叠分类
+    pub fn build_export_html(config: &Config, fonts: &[EmbeddedFont]) -> Result<String> {
+        let palette = config.theme.palette();
+        let categories: Vec<ExportCategoryView> = category_views(&config.category, &palette)
+            .into_iter()
+            .map(|view| ExportCategoryView {
+                keywords: search::index_text([view.category.name.as_str()]),
+                plugin_keywords: view.category.plugins.iter().map(plugin_keywords).collect(),
+                view,
+            })
+            .collect();
+
+        let tera = templates()?;
+        let mut ctx = Context::from_serialize(config)?;
+        ctx.insert("category", &categories);
+        ctx.insert("theme", &palette);
+        ctx.insert("fonts", fonts);
+        Ok(tera.render("export.html", &ctx)?)
+    }
+
+    /// 插件的可搜索文本：名称、说明、别名、标签与指令
+    fn plugin_keywords(plugin: &PluginItem) -> String {
+        let texts = [plugin.name.as_str(), plugin.desc.as_str()]
+            .into_iter()
+            .chain(plugin.aliases.iter().map(String::as_str))
+            .chain(plugin.tags.iter().map(String::as_str))
+            .chain(plugin.commands.iter().flat_map(|cmd| cmd.search_terms()));
+        search::index_text(texts)
+    }
+
+    /// 生成插件详情页 HTML
+    pub fn build_detail_html(
+        config: &Config,
+        category: &Category,
+        plugin: &PluginItem,
+    ) -> Result<String> {
+        let tera = templates()?;
+        let mut ctx = Context::new();
+        ctx.insert("theme", &config.theme.palette());
+        ctx.insert("render", &config.render);
+        ctx.insert("footer", &config.footer);
+        ctx.insert("category", category);
+        ctx.insert("plugin", plugin);
+        Ok(tera.render("detail.html", &ctx)?)
+    }
+
+    /// 生成搜索结果 HTML
+    pub fn build_search_html(
+        config: &Config,
+        keyword: &str,
+        results: &[SearchResult],
+    ) -> Result<String> {
+        let items: Vec<SearchItemView> = results
+            .iter()
+            .take(SEARCH_IMAGE_LIMIT)
+            .map(|r| SearchItemView {
+                result: r,
+                field: r.matched_field.label(),
+                name: highlight(&r.plugin, keyword),
+                desc: highlight(&r.desc, keyword),
+                cmd: r.matched_cmd.as_deref().map(|cmd| highlight(cmd, keyword)),
+            })
+            .collect();
+
+        let tera = templates()?;
+        let mut ctx = Context::new();
+        ctx.insert("theme", &config.theme.palette());
+        ctx.insert("render", &config.render);
+        ctx.insert("footer", &config.footer);
+        ctx.insert("keyword", keyword);
+        ctx.insert("total", &results.len());
+        ctx.insert("remaining", &(results.len() - items.len()));
+        ctx.insert("items", &items);
+        Ok(tera.render("search.html", &ctx)?)
+    }
+
+    /// 影响输出图片的渲染参数摘要，参与缓存键计算
+    pub fn options_fingerprint(settings: &RenderSetting) -> String {
+        format!(
+            "w{}x{}q{}{}-{:?}",
+            settings.width,
+            settings.scale,
+            settings.quality,
+            settings.format.extension(),
+            settings.layout
+        )
+    }
+
+    /// 熔断器：连续失败次数与熔断结束时间
+    struct Breaker {
+        failures: u32,
+        open_until: Option<Instant>,
+    }
+
+    static BREAKER: Mutex<Breaker> = Mutex::new(Breaker {
+        failures: 0,
+        open_until: None,
+    });
+
+    /// 按配置的尺寸与格式渲染为图片
+    ///
+    /// 每次尝试都有超时限制，超时后关闭该标签页；失败后检查浏览器，仅在无响应时重启，然后重试；
+    /// 连续失败达到阈值时熔断，熔断期间直接返回错误，由调用方回退为文本菜单。
+    pub async fn render_image(html: &str, output: &Path, settings: &RenderSetting) -> Result<()> {
+        if let Some(until) = BREAKER.lock().unwrap().open_until
+            && let Some(left) = until.checked_duration_since(Instant::now())
+        {
+            anyhow::bail!("渲染已熔断，{} 秒后恢复", left.as_secs() + 1);
+        }
+
+        let attempts = settings.retries + 1;
+        let mut last_error = None;
+        for attempt in 1..=attempts {
+            let generation = GENERATION.load(Ordering::SeqCst);
+            match capture_with_timeout(html, settings).await {
+                Ok(base64) => {
+                    record(true, settings);
+                    let bytes =
+                        base64::Engine::decode(&base64::engine::general_purpose::STANDARD, base64)?;
+                    return cache::write_atomic(output, &bytes).await;
+                }
+                Err(e) => {
+                    log::warn!(
+                        "[help-center] 第 {}/{} 次渲染失败: {:#}",
+                        attempt,
+                        attempts,
+                        e
+                    );
+                    last_error = Some(e);
+                    recover_browser(generation).await;
+                    if attempt < attempts {
+                        tokio::time::sleep(Duration::from_millis(500 * attempt as u64)).await;
+                    }
+                }
+            }
+        }
+
+        record(false, settings);
+        Err(last_error.unwrap_or_else(|| anyhow::anyhow!("渲染失败")))
+    }
+
+    /// 记录渲染结果，连续失败达到阈值时打开熔断
+    fn record(success: bool, settings: &RenderSetting) {
+        let mut breaker = BREAKER.lock().unwrap();
+        if success {
+            if breaker.failures >= settings.breaker_threshold && settings.breaker_threshold > 0 {
+                log::info!("[help-center] 渲染已恢复");
+            }
+            breaker.failures = 0;
+            breaker.open_until = None;
+            return;
+        }
+
+        breaker.failures += 1;
+        if settings.breaker_threshold > 0 && breaker.failures >= settings.breaker_threshold {
+            breaker.open_until =
+                Some(Instant::now() + Duration::from_secs(settings.breaker_cooldown));
+            log::error!(
+                "[help-center] 渲染连续失败 {} 次，暂停渲染 {} 秒并改用文本菜单",
+                breaker.failures,
+                settings.breaker_cooldown
+            );
+        }
+    }
+
+    /// 浏览器重启次数，用于判断失败之后是否已有其他渲染重启过浏览器
+    static GENERATION: AtomicU64 = AtomicU64::new(0);
+
+    /// 串行化健康检查与重启，避免并发渲染同时重启浏览器
+    static RESTART: AsyncMutex<()> = AsyncMutex::const_new(());
+
+    /// 健康检查与关闭浏览器的超时时间
+    const BROWSER_CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
+    /// 渲染失败后检查浏览器，仅在其无响应时重启
+    ///
+    /// 浏览器由所有渲染共享，单个页面超时或出错不代表浏览器有问题，贸然重启会中断其他进行中的截图。
+    /// `generation` 为本次尝试开始时的重启次数，期间已有其他渲染重启过则不再重复检查。
+    async fn recover_browser(generation: u64) {
+        let _guard = RESTART.lock().await;
+        if GENERATION.load(Ordering::SeqCst) != generation {
+            return;
+        }
+        if browser_responsive().await {
+            return;
+        }
+
+        log::warn!("[help-center] 浏览器无响应，正在重启");
+        if tokio::time::timeout(BROWSER_CHECK_TIMEOUT, Browser::shutdown_global())
+            .await
+            .is_err()
+        {
+            log::warn!("[help-center] 关闭浏览器超时");
+        }
+        GENERATION.fetch_add(1, Ordering::SeqCst);
+    }
+
+    /// 健康检查：能否在限定时间内打开并关闭一个标签页
+    async fn browser_responsive() -> bool {
+        // 未安装浏览器时 `Browser::instance` 会 panic，放在独立任务中执行。
+        // 渲染也供命令行工具使用，不在插件上下文中，因此直接使用 tokio 任务
+        let task = tokio::spawn(async {
+            let tab = Browser::instance().await.new_tab().await?;
+            tab.close().await
+        });
+        let abort = task.abort_handle();
+        match tokio::time::timeout(BROWSER_CHECK_TIMEOUT, task).await {
+            Ok(Ok(Ok(()))) => true,
+            Ok(_) => false,
+            Err(_) => {
+                abort.abort();
+                false
+            }
+        }
+    }
+
+    /// 在独立任务中截图：超时后关闭标签页并返回错误，浏览器启动时的 panic 转为错误
+    async fn capture_with_timeout(html: &str, settings: &RenderSetting) -> Result<String> {
+        let timeout = settings.timeout;
+        let deadline = tokio::time::Instant::now() + Duration::from_secs(timeout);
+        // 与健康检查相同，命令行工具中没有插件上下文，不能使用 `kovi::spawn`
+        let task = tokio::spawn(capture(
+            html.to_string(),
+            viewport(settings),
+            capture_options(settings),
+            deadline,
+        ));
+        let abort = task.abort_handle();
+
+        // 任务内部在截止时间到达时会自行关闭标签页，这里额外留出关闭的时间，
+        // 仅在启动浏览器或打开标签页卡住时才强制中止
+        match tokio::time::timeout_at(deadline + BROWSER_CHECK_TIMEOUT, task).await {
+            Ok(Ok(result)) => result,
+            Ok(Err(e)) if e.is_panic() => {
+                let payload = e.into_panic();
+                let cause = payload
+                    .downcast_ref::<String>()
+                    .map(String::as_str)
+                    .or_else(|| payload.downcast_ref::<&str>().copied())
+                    .unwrap_or("未知错误");
+                anyhow::bail!("浏览器启动失败，请确认已安装 Chrome 或 Chromium: {}", cause)
+            }
+            Ok(Err(e)) => Err(e.into()),
+            Err(_) => {
+                abort.abort();
+                anyhow::bail!("渲染超时（{} 秒）", timeout)
+            }
+        }
+    }
+
+    fn capture_options(settings: &RenderSetting) -> CaptureOptions {
+        let format = match settings.format {
+            config::ImageFormat::Png => ImageFormat::Png,
+            config::ImageFormat::Jpeg => ImageFormat::Jpeg,
+            config::ImageFormat::Webp => ImageFormat::WebP,
+        };
+        let mut opts = CaptureOptions::new()
+            .with_viewport(viewport(settings))
+            .with_format(format)
+            .with_full_page(true);
+        if format != ImageFormat::Png {
+            opts = opts.with_quality(settings.quality);
+        }
+        opts
+    }
+
+    fn viewport(settings: &RenderSetting) -> Viewport {
+        Viewport::new(settings.width, 100).with_device_scale_factor(settings.scale)
+    }
+
+    /// 在新标签页中截图，无论成功、出错还是超过截止时间都会关闭标签页
+    async fn capture(
+        html: String,
+        viewport: Viewport,
+        opts: CaptureOptions,
+        deadline: tokio::time::Instant,
+    ) -> Result<String> {
+        let browser = Browser::instance().await;
+        let tab = tokio::time::timeout_at(deadline, browser.new_tab())
+            .await
+            .map_err(|_| anyhow::anyhow!("渲染超时：打开标签页无响应"))??;
+
+        let shot = async {
+            tab.set_viewport(&viewport).await?;
+            tab.set_content(&html).await?;
+            tab.find_element("body")
+                .await?
+                .screenshot_with_options(opts)
+                .await
+        };
+        let result = tokio::time::timeout_at(deadline, shot).await;
+
+        let _ = tokio::time::timeout(BROWSER_CHECK_TIMEOUT, tab.close()).await;
+        result.map_err(|_| anyhow::anyhow!("渲染超时"))?
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use crate::config::test_config;
+
+        #[test]
+        fn highlight_escapes_before_marking() {
+            assert_eq!(
+                highlight("roll <count> [sides]", "ROLL"),
+                "<mark>roll</mark> &lt;count&gt; [sides]"
+            );
+            assert_eq!(highlight("a < b & c", "b"), "a &lt; <mark>b</mark> &amp; c");
+            assert_eq!(highlight("<b>", "<b"), "<mark>&lt;b</mark>&gt;");
+        }
+
+        #[test]
+        fn templates_escape_user_text() {
+            let plugin = PluginItem::new("骰子", "a < b <script>x</script>")
+                .with_command("roll <count> [sides]")
+                .with_note("<i>注意</i>");
+            let config = test_config(vec![Category::new("工具").with_plugin(plugin.clone())]);
+
+            let page = &paginate(&config)[0];
+            let detail = build_detail_html(&config, &config.category[0], &plugin).unwrap();
+            let results = config.search("roll");
+            let search = build_search_html(&config, "roll", &results).unwrap();
+
+            let export = build_export_html(&config, &[]).unwrap();
+
+            for html in [build_html(&config, page).unwrap(), detail, search, export] {
+                assert!(!html.contains("<script>x"), "{}", html);
+                assert!(!html.contains("<count>"));
+                assert!(html.contains("a &lt; b"));
+            }
+        }
+
+        fn long_menu(categories: usize, plugins: usize) -> Config {
+            test_config(
+                (0..categories)
+                    .map(|i| {
+                        (0..plugins).fold(Category::new(format!("分类{}", i)), |cat, j| {
+                            cat.with_plugin(
+                                PluginItem::new(format!("插件{}-{}", i, j), "描述")
+                                    .with_command("指令"),
+                            )
+                        })
+                    })
+                    .collect(),
+            )
+        }
+
+        /// 各页按顺序拼接后的插件名，以及是否存在空页或空分类
+        fn flatten(pages: &[Page]) -> Vec<String> {
+            let mut names = Vec::new();
+            for page in pages {
+                assert!(!page.categories.is_empty(), "第 {} 页为空", page.number);
+                for category in &page.categories {
+                    assert!(!category.plugins.is_empty(), "{} 为空", category.name);
+                    names.extend(category.plugins.iter().map(|p| p.name.clone()));
+                }
+            }
+            names
+        }
+
+        #[test]
+        fn paginate_splits_at_max_height() {
+            let mut config = long_menu(4, 6);
+            config.pagination.max_height = 0;
+            let unpaged = paginate(&config);
+            assert_eq!(unpaged.len(), 1);
+            let all = flatten(&unpaged);
+
+            config.pagination.max_height = 1200;
+            let pages = paginate(&config);
+            assert!(pages.len() > 1);
+            assert_eq!(flatten(&pages), all);
+
+            let columns = grid_columns(&config.render);
+            let limit = PAGE_CHROME_HEIGHT + (1200 - PAGE_CHROME_HEIGHT) * columns;
+            for (i, page) in pages.iter().enumerate() {
+                assert_eq!((page.number, page.total), (i + 1, pages.len()));
+                let height: u32 = PAGE_CHROME_HEIGHT
+                    + page
+                        .categories
+                        .iter()
+                        .map(|c| CATEGORY_HEIGHT + c.plugins.iter().map(plugin_height).sum::<u32>())
+                        .sum::<u32>();
+                assert!(height <= limit, "第 {} 页高度 {}", page.number, height);
+            }
+        }
+
+        #[test]
+        fn paginate_continues_split_categories() {
+            let mut config = long_menu(2, 5);
+            config.pagination.max_plugins = 3;
+            let pages = paginate(&config);
+            assert_eq!(flatten(&pages).len(), 10);
+
+            let layout: Vec<(bool, Vec<(&str, usize)>)> = pages
+                .iter()
+                .map(|p| {
+                    let cats = p
+                        .categories
+                        .iter()
+                        .map(|c| (c.name.as_str(), c.plugins.len()))
+                        .collect();
+                    (p.continued, cats)
+                })
+                .collect();
+            assert_eq!(
+                layout,
+                [
+                    (false, vec![("分类0", 3)]),
+                    (true, vec![("分类0", 2), ("分类1", 1)]),
+                    (true, vec![("分类1", 3)]),
+                    (true, vec![("分类1", 1)]),
+                ]
+            );
+        }
+
+        #[test]
+        fn paginate_keeps_oversized_plugin_on_own_page() {
+            let mut config = long_menu(1, 3);
+            config.category[0].plugins[1].desc = "很长的描述".repeat(200);
+            config.pagination.max_height = 600;
+            let pages = paginate(&config);
+            assert_eq!(pages.len(), 3);
+            assert_eq!(flatten(&pages).len(), 3);
+        }
+
+        #[test]
+        fn parses_hex_and_rgb_colors() {
+            let red = Some([255.0, 0.0, 0.0]);
+            for value in [
+                "#f00",
+                "#F00F",
+                "#ff0000",
+                "#ff000080",
+                " rgb(255, 0, 0) ",
+                "rgb(255 0 0 / 50%)",
+                "rgba(255,0,0,0.5)",
+                "rgb(100%, 0%, 0%)",
+                "rgb(300, -5, 0)",
+            ] {
+                assert_eq!(parse_color(value), red, "{}", value);
+            }
+            assert_eq!(to_hex(parse_color("#6366f1").unwrap()), "#6366f1");
+
+            for value in ["red", "hsl(0, 100%, 50%)", "#ff000", "#ggg", "rgb(1, 2)"] {
+                assert_eq!(parse_color(value), None, "{}", value);
+            }
+        }
+
+        #[test]
+        fn accent_text_stays_readable() {
+            for (palette, color) in [
+                (config::preset("light").unwrap(), "#ffff00"),
+                (config::preset("light").unwrap(), "#f0f0f0"),
+                (config::preset("dark").unwrap(), "#1e1b4b"),
+                (config::preset("dark").unwrap(), "#000"),
+            ] {
+                let accent = Accent::new(color, &palette);
+                let text = parse_color(&accent.text).unwrap();
+                let tint = parse_color(&accent.tint).unwrap();
+                assert!(
+                    contrast(text, tint) >= 4.5,
+                    "{} on {}",
+                    color,
+                    palette.surface
+                );
+
+                let on = parse_color(&accent.on).unwrap();
+                let base = parse_color(&accent.base).unwrap();
+                assert_ne!(on, base);
+            }
+
+            // 已有足够对比度的颜色保持不变
+            let light = config::preset("light").unwrap();
+            assert_eq!(Accent::new("#1e3a8a", &light).text, "#1e3a8a");
+        }
+
+        #[test]
+        fn unparsed_accent_falls_back_to_css_mix() {
+            let accent = Accent::new("teal", &config::preset("light").unwrap());
+            assert_eq!(accent.base, "teal");
+            assert_eq!(accent.tint, "color-mix(in srgb, teal 14%, var(--surface))");
+        }
+    }
+}
+
+// ============================================================================
+//                              文本菜单
+// ============================================================================
+pub mod text {
+    use super::config::{Category, CommandItem, Config, PluginItem, SearchResult, TextFormat};
+
+    /// 生成文本形式的菜单，用于纯文本回复或图片渲染失败时的回退
+    pub fn menu(config: &Config, categories: &[Category], format: TextFormat) -> String {
+        match format {
+            TextFormat::Plain => plain(config, categories),
+            TextFormat::Markdown => markdown(config, categories),
+        }
+    }
+
+    fn plain(config: &Config, categories: &[Category]) -> String {
+        let mut out = config.title.clone();
+        if let Some(subtitle) = non_empty(config.subtitle.as_deref()) {
+            out.push_str(&format!("\n{}", subtitle));
+        }
+
+        for category in categories {
+            out.push_str(&format!("\n\n{}", heading(category)));
+            for plugin in &category.plugins {
+                out.push_str(&format!("\n▸ {}", plugin_name(plugin)));
+                if !plugin.desc.is_empty() {
+                    out.push_str(&format!("：{}", plugin.desc));
+                }
+                if plugin.disabled {
+                    out.push_str(&disabled_note(plugin.disabled_reason.as_deref()));
+                }
+                for cmd in &plugin.commands {
+                    out.push_str(&format!("\n   · {}", command_line(cmd, |s| s.to_string())));
+                }
+            }
         }
 
-        /* 底部 */
-        .footer {
-            text-align: center;
-            padding: 16px 0 8px;
-            color: #94a3b8;
-            font-size: 12px;
+        if let Some(footer) = non_empty(Some(&config.footer)) {
+            out.push_str(&format!("\n\n{}", footer));
         }
+        out
+    }
 
-        .footer-divider {
-            width: 40px;
-            height: 3px;
-            background: linear-gradient(90deg, transparent, var(--primary), transparent);
-            margin: 0 auto 12px;
-            border-radius: 2px;
-            opacity: 0.4;
+    fn markdown(config: &Config, categories: &[Category]) -> String {
+        let mut out = format!("# {}", config.title);
+        if let Some(subtitle) = non_empty(config.subtitle.as_deref()) {
+            out.push_str(&format!("\n\n> {}", subtitle));
         }
 
-        .tip {
-            margin-top: 8px;
-            font-size: 11px;
-            color: #cbd5e1;
-            font-style: italic;
+        for category in categories {
+            out.push_str(&format!("\n\n## {}\n", heading(category)));
+            for plugin in &category.plugins {
+                if plugin.disabled {
+                    out.push_str(&format!("\n- ~~{}~~", plugin_name(plugin)));
+                } else {
+                    out.push_str(&format!("\n- **{}**", plugin_name(plugin)));
+                }
+                if !plugin.desc.is_empty() {
+                    out.push_str(&format!("：{}", plugin.desc));
+                }
+                if plugin.disabled {
+                    out.push_str(&disabled_note(plugin.disabled_reason.as_deref()));
+                }
+                for cmd in &plugin.commands {
+                    let line = command_line(cmd, |s| format!("`{}`", s.replace('`', "'")));
+                    out.push_str(&format!("\n  - {}", line));
+                }
+            }
         }
-    </style>
-</head>
-<body>
-    <div class="container">
-        <div class="header">
-            <h1 class="title">{{ title }}</h1>
-            {% if subtitle %}
-            <div class="subtitle">{{ subtitle }}</div>
-            {% else %}
-            <div class="subtitle">Command Reference</div>
-            {% endif %}
-        </div>
 
-        {% for cat in category %}
-        <div class="category-section">
-            <div class="category-header">
-                <div class="category-icon">{{ cat.icon | default(value="📦") }}</div>
-                <span class="category-name">{{ cat.name }}</span>
-                <span class="category-count">{{ cat.plugins | length }}</span>
-            </div>
-            <div class="plugins-list">
-                {% for plugin in cat.plugins %}
-                <div class="plugin-card">
-                    <div class="plugin-header">
-                        <span class="plugin-icon">{{ plugin.icon | default(value="⚡") }}</span>
-                        <div class="plugin-info">
-                            <div class="plugin-name">{{ plugin.name }}</div>
-                            <div class="plugin-desc">{{ plugin.desc }}</div>
-                        </div>
-                    </div>
-                    <div class="commands">
-                        {% for cmd in plugin.commands %}
-                        <span class="cmd-tag">{{ cmd }}</span>
-                        {% endfor %}
-                    </div>
-                </div>
-                {% endfor %}
-            </div>
-        </div>
-        {% endfor %}
+        if let Some(footer) = non_empty(Some(&config.footer)) {
+            out.push_str(&format!("\n\n---\n\n{}", footer));
+        }
+        out
+    }
+
+    /// 插件详情的文本形式，用于纯文本回复或详情图渲染失败时的回退
+    pub fn detail(category: &Category, plugin: &PluginItem, format: TextFormat) -> String {
+        let markdown = format == TextFormat::Markdown;
+        let section = |title: &str| {
+            if markdown {
+                format!("\n\n### {}\n", title)
+            } else {
+                format!("\n\n【{}】", title)
+            }
+        };
+        let bullet = if markdown { "\n- " } else { "\n▸ " };
+        let code = |s: &str| {
+            if markdown {
+                format!("`{}`", s.replace('`', "'"))
+            } else {
+                s.to_string()
+            }
+        };
+
+        let mut out = if markdown {
+            format!("# {}", plugin_name(plugin))
+        } else {
+            plugin_name(plugin)
+        };
+        if plugin.disabled {
+            out.push_str(&disabled_note(plugin.disabled_reason.as_deref()));
+        }
+        out.push_str(&format!(
+            "{}分类：{}",
+            if markdown { "\n\n" } else { "\n" },
+            heading(category)
+        ));
+        if !plugin.aliases.is_empty() {
+            out.push_str(&format!("\n别名：{}", plugin.aliases.join("、")));
+        }
+        if !plugin.desc.is_empty() {
+            out.push_str(&format!("\n{}", plugin.desc));
+        }
+
+        if !plugin.commands.is_empty() {
+            out.push_str(&section("指令"));
+            for cmd in &plugin.commands {
+                out.push_str(&format!("{}{}", bullet, command_line(cmd, code)));
+            }
+        }
+        if let Some(usage) = non_empty(plugin.usage.as_deref()) {
+            out.push_str(&section("用法"));
+            out.push_str(&format!("\n{}", usage));
+        }
+        if !plugin.params.is_empty() {
+            out.push_str(&section("参数"));
+            for param in &plugin.params {
+                out.push_str(&format!("{}{}", bullet, code(&param.name)));
+                if !param.desc.is_empty() {
+                    out.push_str(&format!("：{}", param.desc));
+                }
+            }
+        }
+        for (title, items) in [("示例", &plugin.examples), ("注意", &plugin.notes)] {
+            if !items.is_empty() {
+                out.push_str(&section(title));
+                for item in items {
+                    out.push_str(&format!("{}{}", bullet, item));
+                }
+            }
+        }
+        out
+    }
+
+    /// 分类标题：图标与名称，停用的分类附带停用说明
+    fn heading(category: &Category) -> String {
+        let mut heading = match non_empty(Some(&category.icon)) {
+            Some(icon) => format!("{} {}", icon, category.name),
+            None => category.name.clone(),
+        };
+        if category.disabled {
+            heading.push_str(&disabled_note(category.disabled_reason.as_deref()));
+        }
+        heading
+    }
+
+    /// 停用说明，如「（已停用：维护中）」
+    fn disabled_note(reason: Option<&str>) -> String {
+        match non_empty(reason) {
+            Some(reason) => format!("（已停用：{}）", reason),
+            None => "（已停用）".to_string(),
+        }
+    }
+
+    fn plugin_name(plugin: &PluginItem) -> String {
+        match non_empty(plugin.icon.as_deref()) {
+            Some(icon) => format!("{} {}", icon, plugin.name),
+            None => plugin.name.clone(),
+        }
+    }
+
+    /// 单条指令：语法（由 `code` 包装）与可选说明
+    fn command_line(cmd: &CommandItem, code: impl Fn(&str) -> String) -> String {
+        let mut line = code(&cmd.syntax());
+        if let Some(desc) = non_empty(cmd.desc.as_deref()) {
+            line.push_str(&format!(" — {}", desc));
+        }
+        line
+    }
+
+    /// 搜索结果的纯文本形式
+    pub fn search(keyword: &str, results: &[SearchResult]) -> String {
+        let mut msg = format!("🔍 搜索「{}」找到 {} 条结果：\n\n", keyword, results.len());
+
+        for (i, r) in results.iter().take(8).enumerate() {
+            msg.push_str(&format!("{}. 【{}】{}", i + 1, r.category, r.plugin));
+            if r.disabled {
+                msg.push_str(&disabled_note(r.disabled_reason.as_deref()));
+            }
+            msg.push_str(&format!("\n   📝 {}\n", r.desc));
+            match &r.matched_cmd {
+                Some(cmd) => msg.push_str(&format!("   🎯 匹配指令: {}\n", cmd)),
+                None => msg.push_str(&format!("   🎯 匹配{}\n", r.matched_field.label())),
+            }
+            msg.push('\n');
+        }
+
+        if results.len() > 8 {
+            msg.push_str(&format!("...还有 {} 条结果", results.len() - 8));
+        }
+
+        msg.trim().to_string()
+    }
+
+    fn non_empty(s: Option<&str>) -> Option<&str> {
+        s.map(str::trim).filter(|s| !s.is_empty())
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn detail_lists_commands_and_sections() {
+            let plugin = PluginItem::new("签到", "每日签到")
+                .with_command("签到 [天数] (补签)")
+                .with_note("每天一次");
+            let category = Category::new("工具").with_plugin(plugin.clone());
+
+            let plain = detail(&category, &plugin, TextFormat::Plain);
+            assert_eq!(
+                plain,
+                "签到\n分类：工具\n每日签到\n\n【指令】\n▸ 签到 [天数] — 补签\n\n【注意】\n▸ 每天一次"
+            );
+
+            let markdown = detail(&category, &plugin, TextFormat::Markdown);
+            assert!(markdown.starts_with("# 签到\n\n分类：工具"));
+            assert!(markdown.contains("\n### 指令\n\n- `签到 [天数]` — 补签"));
+        }
+    }
+}
+
+// ============================================================================
+//                              缓存管理
+// ============================================================================
+mod cache {
+    use super::config::{ImageFormat, RenderSetting};
+    use super::render;
+    use sha2::{Digest, Sha256};
+    use std::collections::{BTreeMap, VecDeque};
+    use std::path::{Path, PathBuf};
+    use std::sync::{Arc, Mutex};
+
+    use kovi::tokio;
+    use tokio::sync::Mutex as AsyncMutex;
+
+    /// 所有缓存图片的文件名前缀
+    const CACHE_PREFIXES: [&str; 4] = ["help_", "category_", "detail_", SEARCH_PREFIX];
+
+    /// 搜索结果缓存图片的文件名前缀
+    pub const SEARCH_PREFIX: &str = "search_";
+
+    /// 磁盘上最多保留的搜索结果图片数，超出时淘汰最久未使用的
+    ///
+    /// 关键词由群成员任意输入，不设上限时缓存会随不同关键词无限增长。
+    pub const SEARCH_CACHE_LIMIT: usize = 64;
+
+    /// 内容寻址的缓存键：渲染参数与完整 HTML 的 SHA-256（取前 128 位）
+    ///
+    /// 页脚、模板、主题等任何影响输出的改动都会体现在 HTML 中，
+    /// 且摘要与 Rust 版本无关，重启或升级后缓存依然有效。
+    pub fn content_key(html: &str, settings: &RenderSetting) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(render::options_fingerprint(settings).as_bytes());
+        hasher.update([0]);
+        hasher.update(html.as_bytes());
+        hex(&hasher.finalize()[..16])
+    }
+
+    /// 名称的短摘要，用于在文件名中区分不同对象
+    fn short_digest(name: &str) -> String {
+        hex(&Sha256::digest(name.as_bytes())[..4])
+    }
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    /// 缓存文件路径：`{prefix}{key}.{扩展名}`
+    pub fn get_cache_path(
+        data_dir: &Path,
+        prefix: &str,
+        key: &str,
+        format: ImageFormat,
+    ) -> PathBuf {
+        data_dir.join(format!("{}{}.{}", prefix, key, format.extension()))
+    }
+
+    /// 是否为缓存图片文件（任一输出格式）
+    fn is_image(name: &str) -> bool {
+        [".png", ".jpg", ".webp"]
+            .iter()
+            .any(|ext| name.ends_with(ext))
+    }
+
+    /// 临时文件超过此时长未修改即视为写入中断后的残留
+    const STALE_TMP: std::time::Duration = std::time::Duration::from_secs(300);
+
+    /// 是否为 [`write_atomic`] 中断后残留的临时文件
+    ///
+    /// 正在写入的临时文件修改时间较新，不会被误删。
+    fn is_stale_tmp(path: &Path) -> bool {
+        file_name(path).ends_with(".tmp")
+            && std::fs::metadata(path)
+                .and_then(|m| m.modified())
+                .is_ok_and(|t| t.elapsed().is_ok_and(|age| age > STALE_TMP))
+    }
+
+    /// 同一变体（群聊/私聊覆盖配置）的菜单缓存共享的文件名前缀
+    pub fn help_prefix(variant: &str) -> String {
+        format!("help_{}_", variant)
+    }
+
+    /// 菜单第 `page` 页的缓存前缀
+    pub fn page_prefix(variant: &str, page: usize) -> String {
+        format!("{}p{}_", help_prefix(variant), page)
+    }
+
+    /// 同一分类页的缓存共享的文件名前缀
+    pub fn category_prefix(variant: &str, category: &str) -> String {
+        format!("category_{}_{}_", variant, short_digest(category))
+    }
+
+    /// 同一变体下同一插件的详情页缓存共享的文件名前缀
+    ///
+    /// 各群的主题、页脚可能不同，详情页按变体分开缓存，清理旧图片时互不影响。
+    pub fn detail_prefix(variant: &str, plugin: &str) -> String {
+        format!("detail_{}_{}_", variant, short_digest(plugin))
+    }
+
+    /// 同一变体下同一关键词的搜索缓存共享的文件名前缀
+    pub fn search_prefix(variant: &str, keyword: &str) -> String {
+        format!(
+            "{}{}_{}_",
+            SEARCH_PREFIX,
+            variant,
+            short_digest(&keyword.to_lowercase())
+        )
+    }
+
+    /// 路径中的文件名
+    pub fn file_name(path: &Path) -> String {
+        path.file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default()
+    }
+
+    /// 检查缓存是否存在且有效
+    pub fn is_valid(path: &Path) -> bool {
+        path.exists() && path.metadata().map(|m| m.len() > 0).unwrap_or(false)
+    }
+
+    /// 先写入同目录下的临时文件再重命名，避免读到写了一半的图片
+    pub async fn write_atomic(path: &Path, bytes: &[u8]) -> anyhow::Result<()> {
+        let tmp = path.with_file_name(format!("{}.tmp", file_name(path)));
+        if let Err(e) = tokio::fs::write(&tmp, bytes).await {
+            let _ = tokio::fs::remove_file(&tmp).await;
+            return Err(e.into());
+        }
+        if let Err(e) = tokio::fs::rename(&tmp, path).await {
+            let _ = tokio::fs::remove_file(&tmp).await;
+            return Err(e.into());
+        }
+        Ok(())
+    }
+
+    /// 正在渲染的缓存路径，每个路径对应一把异步锁
+    static IN_FLIGHT: Mutex<BTreeMap<PathBuf, Arc<AsyncMutex<()>>>> = Mutex::new(BTreeMap::new());
+
+    /// 同一缓存路径同时只渲染一次：后到的请求等待进行中的渲染完成后直接复用结果
+    ///
+    /// 返回 `true` 表示本次调用实际执行了渲染。
+    pub async fn single_flight<F, Fut>(path: &Path, render: F) -> anyhow::Result<bool>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = anyhow::Result<()>>,
+    {
+        let lock = IN_FLIGHT
+            .lock()
+            .unwrap()
+            .entry(path.to_path_buf())
+            .or_default()
+            .clone();
+
+        let result = {
+            let _guard = lock.lock().await;
+            if is_valid(path) {
+                Ok(false)
+            } else {
+                render().await.map(|_| true)
+            }
+        };
+
+        // 没有其他等待者时移除该路径的锁（表中一份 + 当前持有一份）
+        let mut in_flight = IN_FLIGHT.lock().unwrap();
+        if Arc::strong_count(&lock) <= 2 {
+            in_flight.remove(path);
+        }
+        result
+    }
+
+    /// 内存中的图片缓存，队首为最久未使用
+    static MEMORY: Mutex<VecDeque<(PathBuf, Arc<[u8]>)>> = Mutex::new(VecDeque::new());
+
+    /// 读取缓存图片：先查内存，未命中再读磁盘并放入内存
+    ///
+    /// 内存中最多保留 `capacity` 张图片，超出时淘汰最久未使用的，为 0 时不使用内存缓存。
+    pub async fn load(path: &Path, capacity: usize) -> anyhow::Result<Arc<[u8]>> {
+        let hit = {
+            let mut memory = MEMORY.lock().unwrap();
+            let i = memory.iter().position(|(p, _)| p == path);
+            i.and_then(|i| memory.remove(i)).map(|(_, bytes)| bytes)
+        };
+        let bytes = match hit {
+            Some(bytes) => bytes,
+            None => tokio::fs::read(path).await?.into(),
+        };
+
+        // 放到队尾并按容量淘汰（容量可能随配置重载而变小）
+        let mut memory = MEMORY.lock().unwrap();
+        if !memory.iter().any(|(p, _)| p == path) {
+            memory.push_back((path.to_path_buf(), bytes.clone()));
+        }
+        while memory.len() > capacity {
+            memory.pop_front();
+        }
+        Ok(bytes)
+    }
+
+    /// 从内存缓存中移除满足条件的图片
+    fn evict(f: impl Fn(&Path) -> bool) {
+        MEMORY.lock().unwrap().retain(|(p, _)| !f(p));
+    }
+
+    /// 清理指定前缀的旧缓存及残留的临时文件，保留文件名在 `keep` 中的文件
+    pub async fn cleanup_prefix(data_dir: &Path, prefix: &str, keep: &[String]) {
+        let Ok(mut entries) = tokio::fs::read_dir(data_dir).await else {
+            return;
+        };
+
+        while let Ok(Some(entry)) = entries.next_entry().await {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let stale = (is_image(&name) && !keep.contains(&name)) || is_stale_tmp(&entry.path());
+            if name.starts_with(prefix) && stale {
+                let _ = tokio::fs::remove_file(entry.path()).await;
+                evict(|p| p == entry.path());
+            }
+        }
+    }
+
+    /// 将缓存图片标记为刚使用过（更新修改时间），供 [`trim_prefix`] 按最近使用淘汰
+    pub fn touch(path: &Path) {
+        let _ = std::fs::File::options()
+            .write(true)
+            .open(path)
+            .and_then(|f| f.set_modified(std::time::SystemTime::now()));
+    }
+
+    /// 指定前缀的缓存图片只保留最近使用的 `limit` 张，返回删除的文件数
+    ///
+    /// 残留的临时文件一并删除，不计入返回值。
+    pub async fn trim_prefix(data_dir: &Path, prefix: &str, limit: usize) -> usize {
+        let Ok(mut entries) = tokio::fs::read_dir(data_dir).await else {
+            return 0;
+        };
+
+        let mut files = Vec::new();
+        while let Ok(Some(entry)) = entries.next_entry().await {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if !name.starts_with(prefix) {
+                continue;
+            }
+            if is_stale_tmp(&entry.path()) {
+                let _ = tokio::fs::remove_file(entry.path()).await;
+                continue;
+            }
+            if !is_image(&name) {
+                continue;
+            }
+            let modified = entry.metadata().await.and_then(|m| m.modified());
+            files.push((modified.unwrap_or(std::time::UNIX_EPOCH), entry.path()));
+        }
+
+        // 最近使用的在前
+        files.sort_by_key(|f| std::cmp::Reverse(f.0));
+        let mut removed = 0;
+        for (_, path) in files.into_iter().skip(limit) {
+            if tokio::fs::remove_file(&path).await.is_ok() {
+                evict(|p| p == path);
+                removed += 1;
+            }
+        }
+        removed
+    }
+
+    /// 清除全部缓存图片，返回删除的文件数
+    pub fn clear_all(data_dir: &Path) -> usize {
+        CACHE_PREFIXES
+            .iter()
+            .map(|prefix| clear_prefix(data_dir, prefix))
+            .sum()
+    }
+
+    /// 清除指定前缀的缓存图片及残留的临时文件，返回删除的文件数
+    pub fn clear_prefix(data_dir: &Path, prefix: &str) -> usize {
+        evict(|p| p.starts_with(data_dir) && file_name(p).starts_with(prefix));
+        let mut removed = 0;
+        if let Ok(entries) = std::fs::read_dir(data_dir) {
+            for entry in entries.flatten() {
+                let name = entry.file_name().to_string_lossy().to_string();
+                if name.starts_with(prefix)
+                    && (is_image(&name) || is_stale_tmp(&entry.path()))
+                    && std::fs::remove_file(entry.path()).is_ok()
+                {
+                    removed += 1;
+                }
+            }
+        }
+        removed
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use std::time::{Duration, SystemTime};
+
+        #[tokio::test]
+        async fn trim_keeps_most_recently_used() {
+            let dir = std::env::temp_dir().join(format!("help-center-trim-{}", std::process::id()));
+            std::fs::create_dir_all(&dir).unwrap();
+
+            let base = SystemTime::now() - Duration::from_secs(100);
+            for i in 0..5u64 {
+                let path = dir.join(format!("search_default_{}_k.jpg", i));
+                let file = std::fs::File::create(&path).unwrap();
+                file.set_modified(base + Duration::from_secs(i)).unwrap();
+            }
+            std::fs::write(dir.join("help_default_k.jpg"), b"x").unwrap();
+            // 最旧的一张刚被使用过，应当保留
+            touch(&dir.join("search_default_0_k.jpg"));
+
+            assert_eq!(trim_prefix(&dir, SEARCH_PREFIX, 3).await, 2);
+            let mut left: Vec<String> = std::fs::read_dir(&dir)
+                .unwrap()
+                .map(|e| e.unwrap().file_name().to_string_lossy().to_string())
+                .collect();
+            left.sort();
+            assert_eq!(
+                left,
+                [
+                    "help_default_k.jpg",
+                    "search_default_0_k.jpg",
+                    "search_default_3_k.jpg",
+                    "search_default_4_k.jpg"
+                ]
+            );
+            std::fs::remove_dir_all(&dir).unwrap();
+        }
+
+        #[tokio::test]
+        async fn cleanup_removes_stale_temp_files() {
+            let dir = std::env::temp_dir().join(format!("help-center-tmp-{}", std::process::id()));
+            std::fs::create_dir_all(&dir).unwrap();
+
+            let stale = dir.join("detail_default_a_old.jpg.tmp");
+            let writing = dir.join("detail_default_a_new.jpg.tmp");
+            let file = std::fs::File::create(&stale).unwrap();
+            file.set_modified(SystemTime::now() - STALE_TMP * 2)
+                .unwrap();
+            std::fs::write(&writing, b"x").unwrap();
+            std::fs::write(dir.join("detail_default_a_keep.jpg"), b"x").unwrap();
+
+            let keep = ["detail_default_a_keep.jpg".to_string()];
+            cleanup_prefix(&dir, "detail_default_a_", &keep).await;
+            let mut left: Vec<String> = std::fs::read_dir(&dir)
+                .unwrap()
+                .map(|e| e.unwrap().file_name().to_string_lossy().to_string())
+                .collect();
+            left.sort();
+            assert_eq!(
+                left,
+                ["detail_default_a_keep.jpg", "detail_default_a_new.jpg.tmp"]
+            );
+            std::fs::remove_dir_all(&dir).unwrap();
+        }
+    }
+}
+
+// ============================================================================
+//                              配置监听
+// ============================================================================
+mod watcher {
+    use super::{config::Config, prerender, render};
+    use kovi::{log, tokio};
+    use std::path::{Path, PathBuf};
+    use std::sync::{Arc, RwLock};
+    use std::time::{Duration, SystemTime};
+
+    /// 轮询配置文件的间隔
+    const POLL_INTERVAL: Duration = Duration::from_secs(2);
+    /// 文件停止变化后等待的时间，避免编辑器分多次写入时重复加载
+    const DEBOUNCE: Duration = Duration::from_millis(500);
+
+    /// 文件修改时间与大小，用于判断是否变化
+    type Stamp = Option<(SystemTime, u64)>;
+
+    async fn stamp(path: &Path) -> Stamp {
+        let meta = tokio::fs::metadata(path).await.ok()?;
+        Some((meta.modified().ok()?, meta.len()))
+    }
+
+    /// 模板目录下所有文件的时间戳
+    async fn dir_stamp(dir: &Path) -> Vec<(PathBuf, Stamp)> {
+        let mut stamps = Vec::new();
+        let Ok(mut entries) = tokio::fs::read_dir(dir).await else {
+            return stamps;
+        };
+        while let Ok(Some(entry)) = entries.next_entry().await {
+            let path = entry.path();
+            let s = stamp(&path).await;
+            stamps.push((path, s));
+        }
+        stamps.sort();
+        stamps
+    }
+
+    /// 配置文件与模板目录的整体快照
+    async fn snapshot(path: &Path, templates: &Path) -> (Stamp, Vec<(PathBuf, Stamp)>) {
+        (stamp(path).await, dir_stamp(templates).await)
+    }
+
+    /// 在后台监听配置文件与覆盖模板，变化时自动校验并重载
+    pub fn spawn(config_lock: Arc<RwLock<Config>>, data_dir: PathBuf) {
+        let path = config_lock.read().unwrap().config_path.clone();
+        let templates = render::templates_dir(&data_dir);
+
+        kovi::spawn(async move {
+            let mut last = snapshot(&path, &templates).await;
+            let mut ticker = tokio::time::interval(POLL_INTERVAL);
+
+            loop {
+                ticker.tick().await;
+
+                let mut current = snapshot(&path, &templates).await;
+                if current == last {
+                    continue;
+                }
+
+                // 防抖：等待文件停止变化
+                loop {
+                    tokio::time::sleep(DEBOUNCE).await;
+                    let next = snapshot(&path, &templates).await;
+                    if next == current {
+                        break;
+                    }
+                    current = next;
+                }
+
+                let config_changed = current.0 != last.0;
+                let templates_changed = current.1 != last.1;
+                last = current;
+
+                // 监听关闭期间仍需检查新文件，否则改回 `watch_config = true` 后永远无法生效
+                let watching = config_lock.read().unwrap().watch_config
+                    || (config_changed && last.0.is_some() && watch_reenabled(&path));
+                if !watching {
+                    continue;
+                }
+
+                // 文件被删除时保留当前配置
+                let mut reloaded = false;
+                if config_changed && last.0.is_some() {
+                    reloaded |= reload(&config_lock);
+                }
+                if templates_changed {
+                    reloaded |= reload_templates(&config_lock, &data_dir);
+                }
+                if reloaded {
+                    // 缓存按内容寻址，未变化的图片继续命中；过期的图片由各前缀的清理与搜索缓存上限淘汰
+                    prerender::spawn(&config_lock, &data_dir);
+                }
+            }
+        });
+    }
+
+    /// 配置文件中的 `watch_config` 是否开启（缺省为开启），文件无法解析时视为关闭
+    fn watch_reenabled(path: &Path) -> bool {
+        std::fs::read_to_string(path)
+            .ok()
+            .and_then(|content| content.parse::<kovi::toml::Table>().ok())
+            .is_some_and(|table| {
+                table
+                    .get("watch_config")
+                    .and_then(|v| v.as_bool())
+                    .unwrap_or(true)
+            })
+    }
+
+    /// 重新加载覆盖模板，失败时保留旧模板，返回是否成功
+    fn reload_templates(config_lock: &Arc<RwLock<Config>>, data_dir: &Path) -> bool {
+        let config = config_lock.read().unwrap().merged();
+        match render::load_templates(data_dir, &config) {
+            Ok(count) => {
+                log::info!("[help-center] 模板已自动重载（{} 个覆盖文件）", count);
+                true
+            }
+            Err(e) => {
+                log::warn!("[help-center] 模板变更未生效，继续使用旧模板: {:#}", e);
+                false
+            }
+        }
+    }
+
+    /// 解析新配置并原子替换，解析失败时保留旧配置，返回是否成功
+    fn reload(config_lock: &Arc<RwLock<Config>>) -> bool {
+        let result = config_lock.write().unwrap().reload();
+        match result {
+            Ok(warnings) => {
+                for issue in &warnings {
+                    log::warn!("[help-center] {}", issue);
+                }
+            }
+            Err(e) => {
+                log::warn!("[help-center] 配置文件变更未生效，继续使用旧配置:\n{}", e);
+                return false;
+            }
+        }
 
-        <div class="footer">
-            <div class="footer-divider"></div>
-            <div>{{ footer }}</div>
-            <div class="tip">💡 发送「帮助 关键词」可搜索指令</div>
-        </div>
-    </div>
-</body>
-</html>
-"##;
+        // 缓存按内容寻址，内容变化的图片会在下次发送时重新渲染并替换
+        log::info!("[help-center] 配置已自动重载");
+        true
+    }
 
-    /// 生成 HTML
-    pub fn build_html(config: &Config) -> Result<String> {
-        let mut tera = Tera::default();
-        tera.add_raw_template("help", HTML_TEMPLATE)?;
-        let ctx = Context::from_serialize(config)?;
-        Ok(tera.render("help", &ctx)?)
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn reads_watch_flag_from_new_file() {
+            let path =
+                std::env::temp_dir().join(format!("help-center-watch-{}.toml", std::process::id()));
+            let cases = [
+                ("watch_config = false\n", false),
+                ("watch_config = true\n", true),
+                ("title = \"x\"\n", true),
+                ("watch_config = \n", false),
+            ];
+            for (content, expected) in cases {
+                std::fs::write(&path, content).unwrap();
+                assert_eq!(watch_reenabled(&path), expected, "{}", content);
+            }
+            std::fs::remove_file(&path).unwrap();
+            assert!(!watch_reenabled(&path));
+        }
     }
+}
 
-    /// 渲染为 PNG 图片
-    pub async fn render_to_png(html: &str, output: &Path) -> Result<()> {
-        let browser = Browser::instance().await;
+// ============================================================================
+//                              预渲染
+// ============================================================================
+mod prerender {
+    use super::{config::Config, handler};
+    use kovi::log;
+    use std::path::Path;
+    use std::sync::{Arc, RwLock};
+    use std::time::Instant;
 
-        let opts = CaptureOptions::new()
-            // 宽度设置为 480，配合 2.0 的缩放，生成宽度为 960px 的图片
-            // 在手机上显示时，视觉大小相当于 480px 宽，阅读体验最佳
-            .with_viewport(Viewport::new(480, 100).with_device_scale_factor(2.0))
-            .with_quality(90)
-            .with_full_page(true);
+    /// 在后台预渲染菜单图片，避免启动或重载后的首次请求等待渲染
+    ///
+    /// 以文本回复菜单时不需要图片，也不会启动浏览器。
+    pub fn spawn(config_lock: &Arc<RwLock<Config>>, data_dir: &Path) {
+        let config = config_lock.read().unwrap().clone();
+        if !config.prerender.enabled || config.menu_reply.text_format().is_some() {
+            return;
+        }
 
-        let base64 = browser
-            .capture_html_with_options(html, "body", opts)
-            .await?;
+        let data_dir = data_dir.to_path_buf();
+        kovi::spawn(async move {
+            run(&config, &data_dir).await;
+        });
+    }
 
-        let bytes = base64::Engine::decode(&base64::engine::general_purpose::STANDARD, base64)?;
+    async fn run(config: &Config, data_dir: &Path) {
+        let started = Instant::now();
+        let mut variants = config.variants();
+        if !config.prerender.variants {
+            variants.truncate(1);
+        }
 
-        tokio::fs::write(output, bytes).await?;
-        Ok(())
+        let (mut images, mut failed) = (0, 0);
+        for (cfg, variant) in &variants {
+            if cfg.menu_reply.text_format().is_some() {
+                continue;
+            }
+            let t = Instant::now();
+            match handler::menu_images(cfg, variant, data_dir).await {
+                Ok(paths) => {
+                    images += paths.len();
+                    log::info!(
+                        "[help-center] 预渲染菜单 {}：{} 页，耗时 {} ms",
+                        variant,
+                        paths.len(),
+                        t.elapsed().as_millis()
+                    );
+                }
+                Err(e) => {
+                    failed += 1;
+                    log::warn!("[help-center] 预渲染菜单 {} 失败: {:#}", variant, e);
+                }
+            }
+
+            if !config.prerender.categories {
+                continue;
+            }
+            let t = Instant::now();
+            for category in &cfg.category {
+                match handler::category_image(cfg, variant, category, data_dir).await {
+                    Ok(_) => images += 1,
+                    Err(e) => {
+                        failed += 1;
+                        log::warn!(
+                            "[help-center] 预渲染分类 {}/{} 失败: {:#}",
+                            variant,
+                            category.name,
+                            e
+                        );
+                    }
+                }
+            }
+            log::info!(
+                "[help-center] 预渲染分类 {}：{} 个，耗时 {} ms",
+                variant,
+                cfg.category.len(),
+                t.elapsed().as_millis()
+            );
+        }
+
+        log::info!(
+            "[help-center] 预渲染完成：{} 张图片，{} 项失败，总耗时 {} ms",
+            images,
+            failed,
+            started.elapsed().as_millis()
+        );
     }
 }
 
 // ============================================================================
-//                              缓存管理
+//                              网页导出
 // ============================================================================
-mod cache {
-    use std::path::{Path, PathBuf};
+pub mod export {
+    use super::render::{self, EmbeddedFont};
+    use super::{cache, config::Config};
+    use anyhow::Context;
+    use base64::Engine;
+    use base64::engine::general_purpose::STANDARD;
+    use std::path::{Component, Path, PathBuf};
 
-    use kovi::tokio;
+    /// 导出文件名，位于插件数据目录
+    pub const FILE_NAME: &str = "help.html";
 
-    /// 获取缓存文件路径
-    pub fn get_cache_path(data_dir: &Path, hash: u64) -> PathBuf {
-        data_dir.join(format!("help_{:016x}.png", hash))
+    /// 嵌入网页的字体文件所在目录，位于插件数据目录
+    pub const FONTS_DIR: &str = "fonts";
+
+    /// 可嵌入的字体：扩展名、MIME 类型与 CSS `format()` 名称
+    const FONT_TYPES: [(&str, (&str, &str)); 4] = [
+        ("woff2", ("font/woff2", "woff2")),
+        ("woff", ("font/woff", "woff")),
+        ("ttf", ("font/ttf", "truetype")),
+        ("otf", ("font/otf", "opentype")),
+    ];
+
+    /// 可内联的图片：扩展名与 MIME 类型
+    const IMAGE_TYPES: [(&str, &str); 6] = [
+        ("png", "image/png"),
+        ("jpg", "image/jpeg"),
+        ("jpeg", "image/jpeg"),
+        ("gif", "image/gif"),
+        ("webp", "image/webp"),
+        ("svg", "image/svg+xml"),
+    ];
+
+    /// 生成独立网页：嵌入 `fonts/` 中的字体，引用的本地图片转为 data URI
+    pub fn build(config: &Config, data_dir: &Path) -> anyhow::Result<String> {
+        let fonts = fonts(&data_dir.join(FONTS_DIR))?;
+        let html = render::build_export_html(config, &fonts)?;
+        Ok(inline_images(&html, data_dir))
     }
 
-    /// 检查缓存是否存在且有效
-    pub fn is_valid(path: &Path) -> bool {
-        path.exists() && path.metadata().map(|m| m.len() > 0).unwrap_or(false)
+    /// 将菜单导出为独立网页并写入 `data_dir`，返回文件路径
+    pub async fn write(config: &Config, data_dir: &Path) -> anyhow::Result<PathBuf> {
+        let html = build(config, data_dir)?;
+        let path = data_dir.join(FILE_NAME);
+        cache::write_atomic(&path, html.as_bytes()).await?;
+        Ok(path)
     }
 
-    /// 清理旧缓存（保留当前 hash 的文件）
-    pub async fn cleanup(data_dir: &Path, current_hash: u64) {
-        let current_name = format!("help_{:016x}.png", current_hash);
+    fn data_uri(mime: &str, bytes: &[u8]) -> String {
+        format!("data:{};base64,{}", mime, STANDARD.encode(bytes))
+    }
 
-        let Ok(mut entries) = tokio::fs::read_dir(data_dir).await else {
-            return;
+    /// 按扩展名（不区分大小写）查找类型
+    fn by_extension<T: Copy>(path: &Path, types: &[(&str, T)]) -> Option<T> {
+        let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+        types.iter().find(|(e, _)| *e == ext).map(|(_, t)| *t)
+    }
+
+    /// 读取目录中的字体文件，字体名取文件名（不含扩展名），目录不存在时为空
+    fn fonts(dir: &Path) -> anyhow::Result<Vec<EmbeddedFont>> {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return Ok(Vec::new());
         };
+        let mut paths: Vec<PathBuf> = entries.flatten().map(|e| e.path()).collect();
+        paths.sort();
+        let mut fonts = Vec::new();
+        for path in paths {
+            let Some((mime, format)) = by_extension(&path, &FONT_TYPES) else {
+                continue;
+            };
+            let bytes = std::fs::read(&path)
+                .with_context(|| format!("读取字体 {} 失败", path.display()))?;
+            fonts.push(EmbeddedFont {
+                family: path
+                    .file_stem()
+                    .map(|s| s.to_string_lossy().to_string())
+                    .unwrap_or_default(),
+                format,
+                src: data_uri(mime, &bytes),
+            });
+        }
+        Ok(fonts)
+    }
 
-        while let Ok(Some(entry)) = entries.next_entry().await {
-            let name = entry.file_name().to_string_lossy().to_string();
-            if name.starts_with("help_") && name.ends_with(".png") && name != current_name {
-                let _ = tokio::fs::remove_file(entry.path()).await;
+    /// 将 `src="…"` 与 `url(…)` 中引用的本地图片（相对 `base`）替换为 data URI
+    ///
+    /// 网址、绝对路径与不存在的文件保持原样。
+    fn inline_images(html: &str, base: &Path) -> String {
+        let mut out = String::with_capacity(html.len());
+        let mut rest = html;
+        loop {
+            let next = [("src=\"", "\""), ("url(", ")")]
+                .into_iter()
+                .filter_map(|(open, close)| rest.find(open).map(|i| (i + open.len(), close)))
+                .min_by_key(|(i, _)| *i);
+            let Some((start, close)) = next else {
+                break;
+            };
+            let Some(len) = rest[start..].find(close) else {
+                break;
+            };
+            let target = &rest[start..start + len];
+            out.push_str(&rest[..start]);
+            match local_image(target.trim_matches(['\'', '"']), base) {
+                Some(uri) => out.push_str(&uri),
+                None => out.push_str(target),
             }
+            rest = &rest[start + len..];
+        }
+        out.push_str(rest);
+        out
+    }
+
+    fn local_image(target: &str, base: &Path) -> Option<String> {
+        let path = Path::new(target);
+        // 只接受数据目录内的相对路径
+        if target.contains(':') || !path.components().all(|c| matches!(c, Component::Normal(_))) {
+            return None;
+        }
+        let mime = by_extension(path, &IMAGE_TYPES)?;
+        let bytes = std::fs::read(base.join(path)).ok()?;
+        Some(data_uri(mime, &bytes))
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use crate::config::test_config;
+
+        #[test]
+        fn embeds_fonts_and_local_images() {
+            let dir =
+                std::env::temp_dir().join(format!("help-center-export-{}", std::process::id()));
+            std::fs::create_dir_all(dir.join(FONTS_DIR)).unwrap();
+            std::fs::write(dir.join(FONTS_DIR).join("Demo Sans.woff2"), b"font").unwrap();
+            std::fs::write(dir.join(FONTS_DIR).join("readme.txt"), b"x").unwrap();
+            std::fs::write(dir.join("logo.png"), b"img").unwrap();
+
+            let html = build(&test_config(Vec::new()), &dir).unwrap();
+            assert_eq!(html.matches("@font-face").count(), 1);
+            assert!(html.contains("src: url(data:font/woff2;base64,Zm9udA==) format(\"woff2\")"));
+            assert!(html.contains("font-family: \"Demo Sans\", 'HarmonyOS Sans SC'"));
+
+            let page = "<img src=\"logo.png\"><img src=\"https://a.b/logo.png\">\
+                        <i style=\"background: url('logo.png')\"></i><img src=\"../logo.png\">";
+            assert_eq!(
+                inline_images(page, &dir),
+                "<img src=\"data:image/png;base64,aW1n\"><img src=\"https://a.b/logo.png\">\
+                 <i style=\"background: url(data:image/png;base64,aW1n)\"></i><img src=\"../logo.png\">"
+            );
+            std::fs::remove_dir_all(&dir).unwrap();
         }
     }
 }
@@ -574,98 +6250,453 @@ mod cache {
 //                              消息处理
 // ============================================================================
 mod handler {
-    use super::{cache, config, render};
-    use kovi::{Message, MsgEvent, log, tokio};
-    use std::path::Path;
-    use std::sync::{Arc, RwLock};
+    use super::{cache, config, export, prerender, render, text, validate};
+    use anyhow::Context;
+    use kovi::bot::runtimebot::CanSendApi;
+    use kovi::serde_json::json;
+    use kovi::{Message, MsgEvent, log};
+    use std::collections::VecDeque;
+    use std::path::{Path, PathBuf};
+    use std::sync::{Arc, Mutex, RwLock};
+    use std::time::{Duration, Instant};
+
+    /// 消息来源
+    pub fn scope_of(event: &MsgEvent) -> config::Scope {
+        match event.group_id {
+            Some(id) => config::Scope::Group(id),
+            None => config::Scope::Private,
+        }
+    }
+
+    /// 发送者的查看身份
+    ///
+    /// `bot_admins` 为 Kovi 配置中的机器人管理员，始终视为管理员。
+    pub fn viewer_of(
+        event: &MsgEvent,
+        config_lock: &Arc<RwLock<config::Config>>,
+        bot_admins: &[i64],
+    ) -> config::Viewer {
+        let admin = bot_admins.contains(&event.user_id) || {
+            let cfg = config_lock.read().unwrap();
+            cfg.is_admin(event.user_id, event.sender.role.as_deref())
+        };
+        if admin {
+            config::Viewer::Admin
+        } else {
+            config::Viewer::Member
+        }
+    }
+
+    /// 解析消息来源与查看者对应的可见配置与缓存变体名
+    fn resolve(
+        event: &MsgEvent,
+        config_lock: &Arc<RwLock<config::Config>>,
+        viewer: config::Viewer,
+    ) -> (config::Config, String) {
+        config_lock.read().unwrap().view(scope_of(event), viewer)
+    }
 
     /// 处理帮助指令
     pub async fn handle_help(
         event: &Arc<MsgEvent>,
         config_lock: &Arc<RwLock<config::Config>>,
+        viewer: config::Viewer,
         data_dir: &Path,
     ) {
-        let (config, hash) = {
-            let cfg = config_lock.read().unwrap();
-            (cfg.clone(), cfg.content_hash())
+        let (config, variant) = resolve(event, config_lock, viewer);
+        if let Some(format) = config.menu_reply.text_format() {
+            event.reply(text::menu(&config, &config.category, format));
+            return;
+        }
+
+        let urls = match menu_images(&config, &variant, data_dir).await {
+            Ok(paths) => image_urls(&config, &paths).await,
+            Err(e) => Err(e),
+        };
+        let urls = match urls {
+            Ok(urls) => urls,
+            Err(e) => return reply_fallback(event, &config, &config.category, e),
+        };
+
+        match (urls.as_slice(), config.pagination.send) {
+            ([url], _) => send_image(event, url),
+            (_, config::PageSend::Images) => {
+                for (i, url) in urls.iter().enumerate() {
+                    if i == 0 {
+                        send_image(event, url);
+                    } else {
+                        event.reply(Message::new().add_image(url));
+                    }
+                }
+            }
+            (_, config::PageSend::Forward) => send_forward(event, &config.title, &urls),
+        }
+    }
+
+    /// 处理分类指令，关键词未匹配分类名或序号时返回 `false`
+    pub async fn handle_category(
+        event: &Arc<MsgEvent>,
+        keyword: &str,
+        config_lock: &Arc<RwLock<config::Config>>,
+        viewer: config::Viewer,
+        data_dir: &Path,
+    ) -> bool {
+        let (config, variant) = resolve(event, config_lock, viewer);
+        let Some(category) = config.find_category(keyword) else {
+            return false;
         };
+        let categories = std::slice::from_ref(category);
+        if let Some(format) = config.menu_reply.text_format() {
+            event.reply(text::menu(&config, categories, format));
+            return true;
+        }
 
-        let cache_path = cache::get_cache_path(data_dir, hash);
+        let url = match category_image(&config, &variant, category, data_dir).await {
+            Ok(path) => image_url(&config, &path).await,
+            Err(e) => Err(e),
+        };
+        match url {
+            Ok(url) => send_image(event, &url),
+            Err(e) => reply_fallback(event, &config, categories, e),
+        }
+        true
+    }
 
-        // 检查缓存
-        if !cache::is_valid(&cache_path) {
-            // 发送临时消息提示（可选，如果生成很快也可以不发）
-            // event.reply("🎨 正在生成帮助菜单...");
+    /// 取得菜单各页的图片，缺失的页单独渲染
+    pub async fn menu_images(
+        config: &config::Config,
+        variant: &str,
+        data_dir: &Path,
+    ) -> anyhow::Result<Vec<PathBuf>> {
+        let mut paths = Vec::new();
+        let mut rendered = false;
+        for page in render::paginate(config) {
+            let html = render::build_html(config, &page);
+            let prefix = cache::page_prefix(variant, page.number);
+            let (path, new) = cached_image(config, html, data_dir, &prefix).await?;
+            rendered |= new;
+            paths.push(path);
+        }
 
-            // 生成 HTML
-            let html = match render::build_html(&config) {
-                Ok(h) => h,
-                Err(e) => {
-                    log::error!("HTML 生成失败: {}", e);
-                    event.reply("❌ 菜单生成失败，请稍后重试");
-                    return;
+        if rendered {
+            // 异步清理旧版本及多余的页
+            spawn_cleanup(data_dir, cache::help_prefix(variant), &paths);
+        }
+        Ok(paths)
+    }
+
+    /// 取得单个分类页的图片
+    pub async fn category_image(
+        config: &config::Config,
+        variant: &str,
+        category: &config::Category,
+        data_dir: &Path,
+    ) -> anyhow::Result<PathBuf> {
+        let html = render::build_category_html(config, category);
+        let prefix = cache::category_prefix(variant, &category.name);
+        let (path, new) = cached_image(config, html, data_dir, &prefix).await?;
+        if new {
+            // 异步清理该分类的旧图片
+            spawn_cleanup(data_dir, prefix, std::slice::from_ref(&path));
+        }
+        Ok(path)
+    }
+
+    /// 处理插件详情指令，关键词未精确匹配插件时返回 `false`
+    pub async fn handle_detail(
+        event: &Arc<MsgEvent>,
+        keyword: &str,
+        config_lock: &Arc<RwLock<config::Config>>,
+        viewer: config::Viewer,
+        data_dir: &Path,
+    ) -> bool {
+        let (config, variant) = resolve(event, config_lock, viewer);
+        let Some((category, plugin)) = config.find_plugin(keyword) else {
+            return false;
+        };
+        if let Some(format) = config.menu_reply.text_format() {
+            event.reply(text::detail(category, plugin, format));
+            return true;
+        }
+
+        let html = render::build_detail_html(&config, category, plugin);
+        let prefix = cache::detail_prefix(&variant, &plugin.name);
+        let url = match cached_image(&config, html, data_dir, &prefix).await {
+            Ok((path, new)) => {
+                if new {
+                    // 异步清理该插件的旧详情页
+                    spawn_cleanup(data_dir, prefix, std::slice::from_ref(&path));
                 }
-            };
+                image_url(&config, &path).await
+            }
+            Err(e) => Err(e),
+        };
+        match url {
+            Ok(url) => send_image(event, &url),
+            Err(e) => match config.fallback.text_format() {
+                Some(format) => {
+                    log::warn!("详情图片生成失败，改用文本: {:#}", e);
+                    event.reply(text::detail(category, plugin, format));
+                }
+                None => reply_failure(event, e),
+            },
+        }
+        true
+    }
 
-            // 渲染图片
-            if let Err(e) = render::render_to_png(&html, &cache_path).await {
-                log::error!("图片渲染失败: {}", e);
-                event.reply("❌ 图片渲染失败");
-                return;
+    /// HTML 对应的缓存图片路径，文件名为 `{prefix}{内容键}.{扩展名}`
+    fn cache_path(config: &config::Config, html: &str, data_dir: &Path, prefix: &str) -> PathBuf {
+        let key = cache::content_key(html, &config.render);
+        cache::get_cache_path(data_dir, prefix, &key, config.render.format)
+    }
+
+    /// 取得 HTML 对应的缓存图片，缺失时渲染，返回图片路径与是否为新渲染
+    async fn cached_image(
+        config: &config::Config,
+        html: anyhow::Result<String>,
+        data_dir: &Path,
+        prefix: &str,
+    ) -> anyhow::Result<(PathBuf, bool)> {
+        let html = html.context("HTML 生成失败")?;
+        let settings = &config.render;
+        let cache_path = cache_path(config, &html, data_dir, prefix);
+        if cache::is_valid(&cache_path) {
+            return Ok((cache_path, false));
+        }
+
+        let rendered = cache::single_flight(&cache_path, || {
+            render::render_image(&html, &cache_path, settings)
+        })
+        .await
+        .context("图片渲染失败")?;
+        Ok((cache_path, rendered))
+    }
+
+    /// 记录渲染失败并回复用户
+    fn reply_failure(event: &Arc<MsgEvent>, e: anyhow::Error) {
+        log::error!("菜单生成失败: {:#}", e);
+        event.reply("❌ 菜单生成失败，请稍后重试");
+    }
+
+    /// 菜单图片生成失败时按配置改发文本菜单，未启用回退时提示失败
+    fn reply_fallback(
+        event: &Arc<MsgEvent>,
+        config: &config::Config,
+        categories: &[config::Category],
+        e: anyhow::Error,
+    ) {
+        let Some(format) = config.fallback.text_format() else {
+            return reply_failure(event, e);
+        };
+        log::warn!("菜单图片生成失败，改用文本菜单: {:#}", e);
+        event.reply(text::menu(config, categories, format));
+    }
+
+    /// 后台清理 `prefix` 下除 `keep` 以外的旧缓存
+    fn spawn_cleanup(data_dir: &Path, prefix: String, keep: &[PathBuf]) {
+        let dir = data_dir.to_path_buf();
+        let keep: Vec<String> = keep.iter().map(|p| cache::file_name(p)).collect();
+        kovi::spawn(async move {
+            cache::cleanup_prefix(&dir, &prefix, &keep).await;
+        });
+    }
+
+    /// 本地图片的 `file://` 地址
+    fn file_url(path: &Path) -> String {
+        let path_str = path.to_string_lossy().replace('\\', "/");
+        format!("file:///{}", path_str)
+    }
+
+    /// 按配置的发送方式生成缓存图片的地址
+    async fn image_url(config: &config::Config, path: &Path) -> anyhow::Result<String> {
+        let image = &config.image;
+        Ok(match image.send {
+            config::ImageSend::File => file_url(path),
+            config::ImageSend::Base64 => {
+                let bytes = cache::load(path, image.memory_cache)
+                    .await
+                    .context("读取缓存图片失败")?;
+                let encoded =
+                    base64::Engine::encode(&base64::engine::general_purpose::STANDARD, bytes);
+                format!("base64://{}", encoded)
             }
+            config::ImageSend::Http => format!(
+                "{}/{}",
+                image.base_url.trim().trim_end_matches('/'),
+                cache::file_name(path)
+            ),
+        })
+    }
 
-            // 异步清理旧缓存
-            let dir = data_dir.to_path_buf();
-            tokio::spawn(async move {
-                cache::cleanup(&dir, hash).await;
-            });
+    /// 依次生成多张图片的地址
+    async fn image_urls(config: &config::Config, paths: &[PathBuf]) -> anyhow::Result<Vec<String>> {
+        let mut urls = Vec::with_capacity(paths.len());
+        for path in paths {
+            urls.push(image_url(config, path).await?);
         }
+        Ok(urls)
+    }
 
-        // 发送图片
-        let path_str = cache_path.to_string_lossy().replace('\\', "/");
-        let msg = Message::new()
-            .add_reply(event.message_id)
-            .add_image(&format!("file:///{}", path_str));
+    /// 以回复形式发送图片
+    fn send_image(event: &Arc<MsgEvent>, url: &str) {
+        let msg = Message::new().add_reply(event.message_id).add_image(url);
         event.reply(msg);
     }
 
+    /// 将多张图片合并为一条转发消息发送
+    fn send_forward(event: &Arc<MsgEvent>, name: &str, urls: &[String]) {
+        let nodes: Vec<_> = urls
+            .iter()
+            .map(|url| {
+                json!({
+                    "type": "node",
+                    "data": {
+                        "name": name,
+                        "nickname": name,
+                        "uin": event.self_id.to_string(),
+                        "user_id": event.self_id.to_string(),
+                        "content": [{ "type": "image", "data": { "file": url } }],
+                    }
+                })
+            })
+            .collect();
+
+        match event.group_id {
+            Some(group_id) => event.send_api(
+                "send_group_forward_msg",
+                json!({ "group_id": group_id, "messages": nodes }),
+            ),
+            None => event.send_api(
+                "send_private_forward_msg",
+                json!({ "user_id": event.user_id, "messages": nodes }),
+            ),
+        }
+    }
+
     /// 处理搜索指令
-    pub fn handle_search(
+    pub async fn handle_search(
         event: &Arc<MsgEvent>,
         keyword: &str,
         config_lock: &Arc<RwLock<config::Config>>,
+        viewer: config::Viewer,
+        data_dir: &Path,
     ) {
-        let results = {
-            let cfg = config_lock.read().unwrap();
-            cfg.search(keyword)
-        };
+        let (config, variant) = resolve(event, config_lock, viewer);
+        let results = config.search(keyword);
 
         if results.is_empty() {
             event.reply(format!("🔍 未找到与「{}」相关的指令", keyword));
             return;
         }
 
-        let mut msg = format!("🔍 搜索「{}」找到 {} 条结果：\n\n", keyword, results.len());
+        if config.search_reply == config::SearchReply::Text {
+            event.reply(text::search(keyword, &results));
+            return;
+        }
 
-        for (i, r) in results.iter().take(8).enumerate() {
-            msg.push_str(&format!(
-                "{}. 【{}】{}\n   📝 {}\n",
-                i + 1,
-                r.category,
-                r.plugin,
-                r.desc
-            ));
-            if let Some(cmd) = &r.matched_cmd {
-                msg.push_str(&format!("   🎯 匹配: {}\n", cmd));
+        let html = render::build_search_html(&config, keyword, &results);
+        let prefix = cache::search_prefix(&variant, keyword);
+
+        // 未缓存的关键词需要新渲染，超出频率限制时直接发送文本结果
+        if let Ok(html) = &html
+            && !cache::is_valid(&cache_path(&config, html, data_dir, &prefix))
+            && !allow_search_render()
+        {
+            log::info!("[help-center] 搜索图片渲染过于频繁，改发文本结果");
+            event.reply(text::search(keyword, &results));
+            return;
+        }
+
+        let url = match cached_image(&config, html, data_dir, &prefix).await {
+            Ok((path, new)) => {
+                if new {
+                    spawn_cleanup(data_dir, prefix, std::slice::from_ref(&path));
+                    let dir = data_dir.to_path_buf();
+                    kovi::spawn(async move {
+                        cache::trim_prefix(&dir, cache::SEARCH_PREFIX, cache::SEARCH_CACHE_LIMIT)
+                            .await;
+                    });
+                } else {
+                    cache::touch(&path);
+                }
+                image_url(&config, &path).await
             }
-            msg.push('\n');
+            Err(e) => Err(e),
+        };
+        match url {
+            Ok(url) => send_image(event, &url),
+            // 图片渲染失败时退回文本结果
+            Err(e) => {
+                log::error!("搜索结果渲染失败: {:#}", e);
+                event.reply(text::search(keyword, &results));
+            }
+        }
+    }
+
+    /// 每分钟最多新渲染的搜索结果图片数，已缓存的关键词不受限制
+    const SEARCH_RENDERS_PER_MINUTE: usize = 10;
+
+    /// 最近一分钟内新渲染搜索图片的时间
+    static SEARCH_RENDERS: Mutex<VecDeque<Instant>> = Mutex::new(VecDeque::new());
+
+    /// 是否允许再新渲染一张搜索结果图片，允许时记入频率统计
+    fn allow_search_render() -> bool {
+        let now = Instant::now();
+        let mut renders = SEARCH_RENDERS.lock().unwrap();
+        while renders
+            .front()
+            .is_some_and(|t| now.duration_since(*t) >= Duration::from_secs(60))
+        {
+            renders.pop_front();
         }
+        if renders.len() >= SEARCH_RENDERS_PER_MINUTE {
+            return false;
+        }
+        renders.push_back(now);
+        true
+    }
 
-        if results.len() > 8 {
-            msg.push_str(&format!("...还有 {} 条结果", results.len() - 8));
+    /// 校验管理权限，无权限时回复提示并返回 `false`
+    pub fn require_admin(event: &Arc<MsgEvent>, viewer: config::Viewer) -> bool {
+        let allowed = viewer == config::Viewer::Admin;
+        if !allowed {
+            log::info!(
+                "[help-center] 用户 {} 尝试执行管理指令，已拒绝",
+                event.user_id
+            );
+            event.reply("🚫 权限不足：该指令仅限管理员使用");
         }
+        allowed
+    }
+
+    /// 处理缓存清除
+    pub fn handle_clear_cache(event: &Arc<MsgEvent>, data_dir: &Path) {
+        let removed = cache::clear_all(data_dir);
+        event.reply(format!("🧹 已清除 {} 张缓存图片", removed));
+    }
 
-        event.reply(msg.trim());
+    /// 处理网页导出，网页可能被公开分享，只包含普通成员可见的条目
+    pub async fn handle_export(
+        event: &Arc<MsgEvent>,
+        config_lock: &Arc<RwLock<config::Config>>,
+        data_dir: &Path,
+    ) {
+        let config = config_lock
+            .read()
+            .unwrap()
+            .merged()
+            .visible_to(config::Viewer::Member);
+        match export::write(&config, data_dir).await {
+            // 只回复文件名，不向聊天暴露服务器上的绝对路径
+            Ok(path) => event.reply(format!(
+                "📄 已导出网页版菜单：插件数据目录下的 {}",
+                cache::file_name(&path)
+            )),
+            Err(e) => {
+                log::error!("网页导出失败: {:#}", e);
+                event.reply("❌ 网页导出失败，请查看日志");
+            }
+        }
     }
 
     /// 处理配置重载
@@ -680,30 +6711,35 @@ mod handler {
         };
 
         match result {
-            Ok(()) => {
-                // 清除所有缓存
-                if let Ok(entries) = std::fs::read_dir(data_dir) {
-                    for entry in entries.flatten() {
-                        let name = entry.file_name().to_string_lossy().to_string();
-                        if name.starts_with("help_") && name.ends_with(".png") {
-                            let _ = std::fs::remove_file(entry.path());
-                        }
-                    }
+            Ok(warnings) => {
+                let config = config_lock.read().unwrap().merged();
+                let template_result = render::load_templates(data_dir, &config);
+
+                // 缓存按内容寻址，只需在后台预渲染变化的图片
+                prerender::spawn(config_lock, data_dir);
+                let mut msg = String::from("✅ 配置重载成功！下次查看帮助将使用新配置");
+                if !warnings.is_empty() {
+                    msg.push_str("\n\n");
+                    msg.push_str(&validate::report(&warnings));
+                }
+                if let Err(e) = template_result {
+                    msg.push_str(&format!("\n\n⚠️ 模板加载失败，继续使用旧模板：\n{:#}", e));
                 }
-                event.reply("✅ 配置重载成功！下次查看帮助将使用新配置");
+                event.reply(msg);
             }
             Err(e) => {
-                event.reply(format!("❌ 配置重载失败: {}", e));
+                event.reply(format!("❌ 配置重载失败，已保留当前配置：\n{}", e));
             }
         }
     }
 
     /// 处理分类列表查询
-    pub fn handle_categories(event: &Arc<MsgEvent>, config_lock: &Arc<RwLock<config::Config>>) {
-        let names = {
-            let cfg = config_lock.read().unwrap();
-            cfg.category_names()
-        };
+    pub fn handle_categories(
+        event: &Arc<MsgEvent>,
+        config_lock: &Arc<RwLock<config::Config>>,
+        viewer: config::Viewer,
+    ) {
+        let names = resolve(event, config_lock, viewer).0.category_names();
 
         if names.is_empty() {
             event.reply("📂 暂无分类配置");
@@ -714,16 +6750,39 @@ mod handler {
         for (i, name) in names.iter().enumerate() {
             msg.push_str(&format!("  {}. {}\n", i + 1, name));
         }
-        msg.push_str("\n💡 发送「帮助 分类名」可搜索该分类下的指令");
+        msg.push_str("\n💡 发送「帮助 分类名」或「帮助 序号」查看该分类的菜单");
         event.reply(msg);
     }
 }
 
+pub use config::{Category, CommandArg, CommandItem, PluginItem, PluginParam};
+pub use registry::HelpRegistry;
+
+/// 将当前生效的帮助菜单（含运行时注册的条目）导出为独立网页，写入插件数据目录
+///
+/// 网页内联全部样式，可直接部署或离线打开，支持搜索与折叠分类；只包含普通成员可见的条目。
+/// 需在插件加载后调用，返回文件路径。
+pub async fn export_html() -> anyhow::Result<std::path::PathBuf> {
+    let config = config::CONFIG
+        .get()
+        .ok_or_else(|| anyhow::anyhow!("帮助插件尚未加载"))?
+        .read()
+        .unwrap()
+        .merged()
+        .visible_to(config::Viewer::Member);
+    let data_dir = config
+        .config_path
+        .parent()
+        .ok_or_else(|| anyhow::anyhow!("无法确定插件数据目录"))?
+        .to_path_buf();
+    export::write(&config, &data_dir).await
+}
+
 use cdp_html_shot::Browser;
 // ============================================================================
 //                              插件入口
 // ============================================================================
-use kovi::{PluginBuilder, tokio};
+use kovi::PluginBuilder;
 
 #[kovi::plugin]
 async fn main() {
@@ -734,16 +6793,40 @@ async fn main() {
     let config_lock = config::Config::load(&data_dir);
     config::CONFIG.set(config_lock.clone()).ok();
 
-    // 预热浏览器（后台异步）
-    tokio::spawn(async {
-        cdp_html_shot::Browser::instance().await;
-        // kovi::log::info!("[help-center] 浏览器预热完成");
-    });
+    // 加载数据目录中的覆盖模板
+    {
+        let config = config_lock.read().unwrap().merged();
+        match render::load_templates(&data_dir, &config) {
+            Ok(0) => {}
+            Ok(count) => kovi::log::info!("[help-center] 已加载 {} 个覆盖模板", count),
+            Err(e) => kovi::log::warn!("[help-center] 覆盖模板加载失败，使用内置模板: {:#}", e),
+        }
+    }
+
+    // 监听配置文件与模板变化
+    watcher::spawn(config_lock.clone(), data_dir.clone());
+
+    // 预热浏览器（后台异步），菜单与搜索均以文本回复时无需启动
+    let uses_images = {
+        let config = config_lock.read().unwrap();
+        config.menu_reply.text_format().is_none()
+            || config.search_reply == config::SearchReply::Image
+    };
+    if uses_images {
+        kovi::spawn(async {
+            cdp_html_shot::Browser::instance().await;
+            // kovi::log::info!("[help-center] 浏览器预热完成");
+        });
+    }
+
+    // 预渲染菜单图片（后台异步）
+    prerender::spawn(&config_lock, &data_dir);
 
     // 消息处理
     PluginBuilder::on_msg(move |event| {
         let config_lock = config_lock.clone();
         let data_dir = data_dir.clone();
+        let bot = bot.clone();
 
         async move {
             let text = match event.borrow_text() {
@@ -751,40 +6834,82 @@ async fn main() {
                 None => return,
             };
 
-            // 获取触发词列表
+            // 获取当前群聊/私聊生效的触发词列表
             let triggers: Vec<String> = {
                 let cfg = config_lock.read().unwrap();
-                cfg.triggers.clone()
+                cfg.triggers_for(handler::scope_of(&event)).to_vec()
             };
 
             let text_lower = text.to_lowercase();
+            // 查看身份需要读取管理员列表，仅在消息命中指令后计算
+            let viewer = || {
+                let bot_admins = bot.get_all_admin().unwrap_or_default();
+                handler::viewer_of(&event, &config_lock, &bot_admins)
+            };
 
             // 1. 检查是否是搜索指令: "帮助 xxx" / "搜索 xxx"
-            for prefix in ["帮助 ", "help ", "搜索 ", "search "] {
+            for prefix in config::SEARCH_PREFIXES {
                 if let Some(keyword) = text_lower.strip_prefix(prefix) {
                     let keyword = keyword.trim();
                     if !keyword.is_empty() {
-                        handler::handle_search(&event, keyword, &config_lock);
+                        let viewer = viewer();
+                        // 精确匹配插件名时发送详情页，匹配分类名或序号时发送分类页，否则发送搜索结果
+                        if !handler::handle_detail(&event, keyword, &config_lock, viewer, &data_dir)
+                            .await
+                            && !handler::handle_category(
+                                &event,
+                                keyword,
+                                &config_lock,
+                                viewer,
+                                &data_dir,
+                            )
+                            .await
+                        {
+                            handler::handle_search(
+                                &event,
+                                keyword,
+                                &config_lock,
+                                viewer,
+                                &data_dir,
+                            )
+                            .await;
+                        }
                         return;
                     }
                 }
             }
 
-            // 2. 检查是否是重载指令（可添加权限检查）
+            // 2. 检查是否是管理指令（需管理员权限）
             if matches!(text_lower.as_str(), "重载帮助" | "reload help" | "帮助重载") {
-                handler::handle_reload(&event, &config_lock, &data_dir);
+                if handler::require_admin(&event, viewer()) {
+                    handler::handle_reload(&event, &config_lock, &data_dir);
+                }
+                return;
+            }
+
+            if matches!(text_lower.as_str(), "导出帮助" | "export help") {
+                if handler::require_admin(&event, viewer()) {
+                    handler::handle_export(&event, &config_lock, &data_dir).await;
+                }
+                return;
+            }
+
+            if matches!(text_lower.as_str(), "清除帮助缓存" | "clear help cache") {
+                if handler::require_admin(&event, viewer()) {
+                    handler::handle_clear_cache(&event, &data_dir);
+                }
                 return;
             }
 
             // 3. 检查是否是查看分类指令
             if matches!(text_lower.as_str(), "分类" | "分类列表" | "categories") {
-                handler::handle_categories(&event, &config_lock);
+                handler::handle_categories(&event, &config_lock, viewer());
                 return;
             }
 
             // 4. 检查是否是帮助指令
             if triggers.iter().any(|t| t.to_lowercase() == text_lower) {
-                handler::handle_help(&event, &config_lock, &data_dir).await;
+                handler::handle_help(&event, &config_lock, viewer(), &data_dir).await;
             }
         }
     });